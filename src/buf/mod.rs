@@ -13,6 +13,7 @@ use crate::plugin_proto::{PluginRequest, PluginResponse, ResponseFile};
 use crate::parser::parse_proto_file;
 use crate::zod::{
     ZodGenerator, ZodGeneratorConfig, ImportStyle,
+    imports::ImportedFile,
     parser::ZodAnnotationParser,
 };
 
@@ -140,6 +141,14 @@ fn process_request(request: PluginRequest) -> Result<(), io::Error> {
     // Create the response
     let mut response_files = Vec::new();
     
+    // Files of the request, whose types are imported by the files importing them
+    let request_files: Vec<ImportedFile> = request.files.iter()
+        .filter_map(|file| {
+            parse_proto_file(&file.content).ok()
+                .map(|proto_file| ImportedFile { path: file.name.clone(), proto_file })
+        })
+        .collect();
+    
     // Process each file in the request
     for file in &request.files {
         info!("Processing file: {}", file.name);
@@ -155,10 +164,16 @@ fn process_request(request: PluginRequest) -> Result<(), io::Error> {
                     import_style: ImportStyle::Named,
                     single_file: true,
                     output_dir: "generated".to_string(),
+                    ..ZodGeneratorConfig::default()
                 };
                 
                 // Generate Zod schemas
-                let generator = ZodGenerator::new(zod_metadata, generator_config);
+                let imported_files: Vec<ImportedFile> = request_files.iter()
+                    .filter(|imported| proto_file.imports.iter().any(|import| import.path == imported.path))
+                    .cloned()
+                    .collect();
+                let generator = ZodGenerator::new(zod_metadata, generator_config)
+                    .with_imports(&file.name, &imported_files);
                 let generated_files = generator.generate(&proto_file);
                 
                 // Add generated files to response
@@ -233,6 +248,7 @@ pub fn process_directory_files(files: &[String]) -> Result<(), io::Error> {
                             import_style: ImportStyle::Named,
                             single_file: true,
                             output_dir: "generated".to_string(),
+                            ..ZodGeneratorConfig::default()
                         };
                         let generator = ZodGenerator::new(zod_metadata, generator_config);
                         let generated_files = generator.generate(&proto_file);
@@ -498,6 +514,7 @@ fn handle_raw_input(buffer: &[u8]) -> Result<(), io::Error> {
                                 import_style: ImportStyle::Named,
                                 single_file: true,
                                 output_dir: "generated".to_string(),
+                                ..ZodGeneratorConfig::default()
                             };
                             let generator = ZodGenerator::new(zod_metadata, generator_config);
                             let generated_files = generator.generate(&proto_file);
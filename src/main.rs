@@ -3,6 +3,7 @@ use protobuf_to_zod::parser::parse_proto_file;
 use protobuf_to_zod::zod::{
     Config, ImportStyle, TargetLanguage, TsImportStyle,
     ZodGenerator, ZodGeneratorConfig, TypeScriptWriter,
+    imports::{load_imports, source_path},
     parser::ZodAnnotationParser,
 };
 use protobuf_to_zod::buf::{self, is_plugin_mode, run_plugin, process_known_files};
@@ -55,6 +56,14 @@ fn main() -> Result<(), Box<dyn Error>> {
             .help("Input proto file")
             .takes_value(true)
             .required(true))
+        .arg(Arg::with_name("proto-path")
+            .short("I")
+            .long("proto-path")
+            .value_name("DIRECTORY")
+            .help("Directory in which to search for imports (default: the current directory)")
+            .takes_value(true)
+            .multiple(true)
+            .number_of_values(1))
         .arg(Arg::with_name("output-dir")
             .short("o")
             .long("output-dir")
@@ -140,14 +149,19 @@ fn main() -> Result<(), Box<dyn Error>> {
     match config.target {
         TargetLanguage::TypeScript => {
             // Create generator config
-            let generator_config = ZodGeneratorConfig {
-                import_style: config.typescript.import_style.into(),
-                single_file: config.typescript.single_file,
-                output_dir: config.output_dir.clone(),
+            let generator_config = ZodGeneratorConfig::from(&config);
+            
+            // Imports are resolved from the proto paths, which the input file is relative to
+            let include_paths: Vec<PathBuf> = match matches.values_of("proto-path") {
+                Some(paths) => paths.map(PathBuf::from).collect(),
+                None => vec![PathBuf::from(".")],
             };
+            let source_path = source_path(proto_path, &include_paths, &env::current_dir()?)?;
+            let imported_files = load_imports(&proto_file, &include_paths);
             
             // Create generator
-            let generator = ZodGenerator::new(zod_metadata, generator_config);
+            let generator = ZodGenerator::new(zod_metadata, generator_config)
+                .with_imports(&source_path, &imported_files);
             
             // Generate schemas
             let generated_files = generator.generate(&proto_file);
@@ -101,7 +101,11 @@ pub struct Field {
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum FieldLabel {
+    /// No label: proto3 implicit presence, the zero value is not serialized
+    Implicit,
+    /// `optional`: explicit presence
     Optional,
+    /// `required` (proto2)
     Required,
     Repeated,
 }
@@ -131,13 +135,14 @@ pub enum FieldType {
 pub struct OneOf {
     pub name: String,
     pub fields: Vec<Field>,
+    pub options: Vec<ProtoOption>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct Enum {
     pub name: String,
     pub values: Vec<EnumValue>,
-    pub options: Vec<EnumValueOption>,
+    pub options: Vec<ProtoOption>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -230,6 +235,16 @@ impl Message {
     }
 }
 
+impl OneOf {
+    pub fn new(name: String) -> Self {
+        OneOf {
+            name,
+            fields: Vec::new(),
+            options: Vec::new(),
+        }
+    }
+}
+
 impl Enum {
     pub fn new(name: String) -> Self {
         Enum {
@@ -360,10 +360,11 @@ pub fn tokenize(input: &str) -> Result<Vec<TokenWithLocation>, ParseError> {
                 pos += len;
                 column += len;
             }
-            '=' | ';' | '{' | '}' | '(' | ')' | '[' | ']' | '<' | '>' | ',' | '.' => {
+            '=' | ';' | ':' | '{' | '}' | '(' | ')' | '[' | ']' | '<' | '>' | ',' | '.' => {
                 let token = match current_char {
                     '=' => Token::Equals,
                     ';' => Token::Semicolon,
+                    ':' => Token::Colon,
                     '{' => Token::OpenBrace,
                     '}' => Token::CloseBrace,
                     '(' => Token::OpenParen,
@@ -526,6 +527,8 @@ fn tokenize_identifier(input: &str) -> (Token, usize) {
         "public" => (Token::Public, end),
         "extensions" => (Token::Extensions, end),
         "stream" => (Token::Stream, end),
+        "optional" => (Token::Optional, end),
+        "required" => (Token::Required, end),
         "string" => (Token::StringType, end),
         "int32" => (Token::Identifier("int32"), end),
         "int64" => (Token::Identifier("int64"), end),
@@ -8,7 +8,7 @@ pub mod error;
 mod lexer;
 
 use crate::parser::ast::{
-    Enum, EnumValue, Field, FieldLabel, Import, ImportKind, Message, Method, OptionValue,
+    Enum, EnumValue, Field, FieldLabel, Import, ImportKind, Message, Method, OneOf, OptionValue,
     ProtoFile, ProtoOption, Service, Syntax,
 };
 
@@ -90,6 +90,7 @@ where
                 let service = parse_service(&mut tokens)?;
                 proto_file.services.push(service);
             }
+            Token::Identifier("extend") => skip_extend(&mut tokens)?,
             Token::Comment(_) => {
                 // Skip comments, but don't error on them
                 tokens.next();
@@ -110,6 +111,34 @@ where
     Ok(proto_file)
 }
 
+/// Skips an `extend` block, such as the declaration of a custom option.
+///
+/// Extensions do not change the shape of the extended messages in JSON, so
+/// nothing is kept of them.
+fn skip_extend<'a, I>(tokens: &mut Peekable<I>) -> Result<(), ParseError>
+where
+    I: Iterator<Item = TokenWithLocation<'a>>,
+{
+    let extend_token = tokens
+        .next()
+        .ok_or_else(|| ParseError::UnexpectedEndOfInput(Location::new(0, 0)))?;
+
+    let mut depth = 0;
+    for token in tokens.by_ref() {
+        match token.token {
+            Token::OpenBrace => depth += 1,
+            Token::CloseBrace => {
+                depth -= 1;
+                if depth == 0 {
+                    return Ok(());
+                }
+            }
+            _ => {}
+        }
+    }
+    Err(ParseError::UnexpectedEndOfInput(extend_token.location))
+}
+
 fn skip_comments_and_whitespace<'a, I>(tokens: &mut Peekable<I>)
 where
     I: Iterator<Item = TokenWithLocation<'a>>,
@@ -427,9 +456,9 @@ where
     let name_token = tokens
         .next()
         .ok_or(ParseError::UnexpectedEndOfInput(message_token.location))?;
-    let name = match &name_token.token {
-        Token::Identifier(s) => s.to_string(),
-        _ => {
+    let name = match identifier_or_keyword(&name_token.token) {
+        Some(name) => name,
+        None => {
             return Err(ParseError::UnexpectedToken(
                 format!("Expected message name, found {:?}", name_token.token),
                 name_token.location,
@@ -483,6 +512,10 @@ where
                 Token::Reserved => {
                     parse_reserved(tokens, &mut message.reserved)?;
                 }
+                Token::Oneof => {
+                    let oneof = parse_oneof(tokens)?;
+                    message.oneofs.push(oneof);
+                }
                 _ => {
                     let field = parse_field(tokens)?;
                     message.fields.push(field);
@@ -496,6 +529,69 @@ where
     Err(ParseError::UnexpectedEndOfInput(open_brace_token.location))
 }
 
+/// Parses a oneof definition from the token stream.
+///
+/// This function expects the next token to be the 'oneof' keyword. It parses
+/// the oneof name and a body of fields and options enclosed in braces.
+///
+/// # Arguments
+///
+/// * `tokens` - A mutable reference to a peekable iterator of TokenWithLocation.
+///
+/// # Returns
+///
+/// * `Result<OneOf, ParseError>` - A Result containing the parsed OneOf on success,
+///   or a ParseError on failure.
+fn parse_oneof<'a, I>(tokens: &mut Peekable<I>) -> Result<OneOf, ParseError>
+where
+    I: Iterator<Item = TokenWithLocation<'a>>,
+{
+    // Expect 'oneof' keyword
+    let oneof_token = tokens
+        .next()
+        .ok_or_else(|| ParseError::UnexpectedEndOfInput(Location::new(0, 0)))?;
+    oneof_token.expect(Token::Oneof)?;
+
+    // Expect oneof name
+    let name = parse_identifier(tokens)?;
+
+    skip_comments_and_whitespace(tokens);
+
+    // Expect opening brace
+    let open_brace_token = tokens
+        .next()
+        .ok_or(ParseError::UnexpectedEndOfInput(oneof_token.location))?;
+    open_brace_token.expect(Token::OpenBrace)?;
+
+    let mut oneof = OneOf::new(name);
+
+    // Parse oneof body
+    loop {
+        skip_comments_and_whitespace(tokens);
+
+        match tokens.peek() {
+            Some(TokenWithLocation {
+                token: Token::CloseBrace,
+                ..
+            }) => {
+                tokens.next(); // Consume closing brace
+                return Ok(oneof);
+            }
+            Some(TokenWithLocation {
+                token: Token::Option,
+                ..
+            }) => {
+                parse_option(tokens, &mut oneof.options)?;
+            }
+            Some(_) => {
+                let field = parse_field(tokens)?;
+                oneof.fields.push(field);
+            }
+            None => return Err(ParseError::UnexpectedEndOfInput(open_brace_token.location)),
+        }
+    }
+}
+
 /// Parses a message definition from the token stream.
 ///
 /// This function expects the 'message' keyword to have already been consumed.
@@ -537,7 +633,14 @@ where
             tokens.next(); // Consume 'required'
             FieldLabel::Required
         }
-        _ => FieldLabel::Optional,
+        Some(TokenWithLocation {
+            token: Token::Optional,
+            ..
+        }) => {
+            tokens.next(); // Consume 'optional'
+            FieldLabel::Optional
+        }
+        _ => FieldLabel::Implicit,
     };
 
     skip_comments_and_whitespace(tokens);
@@ -550,13 +653,9 @@ where
         parse_map_field(tokens)?
     } else {
         // Parse field type
-        let type_token = tokens
-            .next()
-            .ok_or(ParseError::UnexpectedEndOfInput(start_location))?;
-
-        debug!("Parsing field type: {:?}", type_token);
+        let typ = parse_qualified_field_type(tokens)?;
 
-        let typ = parse_field_type(&type_token)?;
+        debug!("Parsed field type: {:?}", typ);
 
         // Parse field name
         let name = parse_field_name(tokens)?;
@@ -587,6 +686,9 @@ where
         }
     };
 
+    // Parse field options, e.g. `[deprecated = true]`
+    let options = parse_field_options(tokens)?;
+
     // Expect semicolon
     tokens
         .next()
@@ -598,14 +700,15 @@ where
         label,
         typ,
         number,
-        options: Vec::new(), // Add options parsing if needed
+        options,
     })
 }
 
 /// Parses a field name from the token stream.
 ///
 /// This function iterates through tokens, building up the field name.
-/// It handles multi-part names (e.g., "message_field") and special cases like "message".
+/// It handles multi-part names (e.g., "message_field") and keywords used as names,
+/// like "message" or "optional".
 /// The parsing stops when it encounters an '=' token, which signifies the end of the field name.
 ///
 /// # Arguments
@@ -620,7 +723,7 @@ where
 ///
 /// Returns a ParseError if:
 /// - Unexpected end of input is encountered
-/// - An unexpected token is found (neither identifier, 'message', nor '=')
+/// - An unexpected token is found (neither identifier, keyword, nor '=')
 fn parse_field_name<'a, I>(tokens: &mut Peekable<I>) -> Result<String, ParseError>
 where
     I: Iterator<Item = TokenWithLocation<'a>>,
@@ -646,19 +749,10 @@ where
                 }
                 tokens.next(); // Consume the token
             }
-            Token::Message if name_parts.is_empty() => {
-                debug!("Found Message token");
-                name_parts.push("message".to_string());
-                tokens.next(); // Consume the token
-            }
-            Token::Map if name_parts.is_empty() => {
-                debug!("Found Map token");
-                name_parts.push("map".to_string());
-                tokens.next(); // Consume the token
-            }
-            Token::Repeated if name_parts.is_empty() => {
-                debug!("Found Repeated token");
-                name_parts.push("repeated".to_string());
+            // Keywords such as `message`, `optional` or `to` are valid names
+            other if name_parts.is_empty() && identifier_or_keyword(other).is_some() => {
+                debug!("Found keyword token: {:?}", other);
+                name_parts.push(other.to_string());
                 tokens.next(); // Consume the token
             }
             Token::Equals => {
@@ -719,9 +813,9 @@ where
     let name_token = tokens
         .next()
         .ok_or(ParseError::UnexpectedEndOfInput(enum_token.location))?;
-    let name = match &name_token.token {
-        Token::Identifier(name) => name.to_string(),
-        _ => {
+    let name = match identifier_or_keyword(&name_token.token) {
+        Some(name) => name,
+        None => {
             return Err(ParseError::UnexpectedToken(
                 format!("Expected enum name, found {:?}", name_token.token),
                 name_token.location,
@@ -763,8 +857,7 @@ where
                 enum_def.values.push(value);
             }
             Token::Option => {
-                let option = parse_enum_option(tokens)?;
-                enum_def.options.push(option);
+                parse_option(tokens, &mut enum_def.options)?;
             }
             Token::Comment(_) => {
                 // Skip comments between enum values
@@ -785,62 +878,6 @@ where
     Err(ParseError::UnexpectedEndOfInput(open_brace_token.location))
 }
 
-fn parse_enum_option<'a, I>(tokens: &mut Peekable<I>) -> Result<EnumValueOption, ParseError>
-where
-    I: Iterator<Item = TokenWithLocation<'a>>,
-{
-    // Consume 'option' token
-    let option_token = tokens
-        .next()
-        .ok_or_else(|| ParseError::UnexpectedEndOfInput(Location::new(0, 0)))?
-        .expect(Token::Option)?;
-
-    // Parse option name
-    let name_token = tokens
-        .next()
-        .ok_or(ParseError::UnexpectedEndOfInput(option_token.location))?;
-    let name = match &name_token.token {
-        Token::Identifier(s) => s.to_string(),
-        _ => {
-            return Err(ParseError::UnexpectedToken(
-                format!("Expected option name, found {:?}", name_token.token),
-                name_token.location,
-            ));
-        }
-    };
-
-    // Expect equals sign
-    tokens
-        .next()
-        .ok_or(ParseError::UnexpectedEndOfInput(name_token.location))?
-        .expect(Token::Equals)?;
-
-    // Parse option value
-    let value_token = tokens
-        .next()
-        .ok_or(ParseError::UnexpectedEndOfInput(name_token.location))?;
-    let value = match &value_token.token {
-        Token::StringLiteral(s) => EnumValueOptionValue::String(s.to_string()),
-        Token::Identifier(s) => EnumValueOptionValue::Identifier(s.to_string()),
-        Token::DecimalIntLiteral(i) => EnumValueOptionValue::DecimalInt(*i),
-        Token::FloatLiteral(f) => EnumValueOptionValue::Float(*f),
-        _ => {
-            return Err(ParseError::UnexpectedToken(
-                format!("Expected option value, found {:?}", value_token.token),
-                value_token.location,
-            ));
-        }
-    };
-
-    // Expect semicolon
-    tokens
-        .next()
-        .ok_or(ParseError::UnexpectedEndOfInput(value_token.location))?
-        .expect(Token::Semicolon)?;
-
-    Ok(EnumValueOption { name, value })
-}
-
 /// Parses an enum value from the token stream.
 ///
 /// This function expects to parse an enum value name, '=' token, and an integer value.
@@ -1015,6 +1052,8 @@ where
         .ok_or(ParseError::UnexpectedEndOfInput(equals_token.location))?;
     let value = match &value_token.token {
         Token::StringLiteral(s) => EnumValueOptionValue::String(s.to_string()),
+        Token::Identifier("true") => EnumValueOptionValue::Bool(true),
+        Token::Identifier("false") => EnumValueOptionValue::Bool(false),
         Token::Identifier(s) => EnumValueOptionValue::Identifier(s.to_string()),
         Token::DecimalIntLiteral(i) => EnumValueOptionValue::DecimalInt(*i),
         Token::FloatLiteral(f) => EnumValueOptionValue::Float(*f),
@@ -1269,7 +1308,7 @@ where
         .expect(Token::Option)?;
 
     // Parse option name (which may include dots)
-    let name = parse_dotted_identifier(tokens)?;
+    let name = parse_option_name(tokens)?;
 
     // Expect equals sign
     tokens
@@ -1291,50 +1330,170 @@ where
     Ok(())
 }
 
-fn parse_dotted_identifier<'a, I>(tokens: &mut Peekable<I>) -> Result<String, ParseError>
+/// Parses an option name from the token stream.
+///
+/// Option names are either plain dotted identifiers (`java_package`) or
+/// extension names wrapped in parentheses, optionally followed by a path into
+/// the extension message (`(buf.validate.field).string.min_len`). The name is
+/// returned as written, e.g. `"(zod.field)"`.
+///
+/// # Arguments
+///
+/// * `tokens` - A mutable reference to a peekable iterator of TokenWithLocation.
+///
+/// # Returns
+///
+/// * `Result<String, ParseError>` - The parsed option name on success, or a ParseError on failure.
+fn parse_option_name<'a, I>(tokens: &mut Peekable<I>) -> Result<String, ParseError>
 where
     I: Iterator<Item = TokenWithLocation<'a>>,
 {
-    let mut parts = Vec::new();
+    let mut name = String::new();
 
     while let Some(token) = tokens.peek() {
         match &token.token {
-            Token::Identifier(s) => {
-                parts.push(s.to_string());
-                tokens.next(); // Consume the identifier
+            Token::OpenParen => {
+                let open_paren_token = tokens.next().unwrap(); // Consume '('
+                name.push('(');
+                name.push_str(&parse_extension_name(tokens, open_paren_token.location)?);
+                name.push(')');
+                tokens
+                    .next()
+                    .ok_or(ParseError::UnexpectedEndOfInput(open_paren_token.location))?
+                    .expect(Token::CloseParen)?;
             }
             Token::Dot => {
-                if parts.is_empty() {
+                if name.is_empty() {
                     return Err(ParseError::UnexpectedToken(
                         "Unexpected dot at the beginning of identifier".to_string(),
                         token.location,
                     ));
                 }
+                name.push('.');
                 tokens.next(); // Consume the dot
             }
-            _ => break,
+            other => match identifier_or_keyword(other) {
+                Some(part) => {
+                    name.push_str(&part);
+                    tokens.next(); // Consume the identifier
+                }
+                None => break,
+            },
         }
     }
 
-    if parts.is_empty() {
+    if name.is_empty() {
         return Err(ParseError::UnexpectedEndOfInput(
             tokens.peek().map_or(Location::new(0, 0), |t| t.location),
         ));
     }
 
-    Ok(parts.join("."))
+    Ok(name)
 }
 
+/// Parses the dotted name of an extension up to (but not including) the
+/// closing parenthesis or bracket, e.g. `buf.validate.field`.
+fn parse_extension_name<'a, I>(
+    tokens: &mut Peekable<I>,
+    start_location: Location,
+) -> Result<String, ParseError>
+where
+    I: Iterator<Item = TokenWithLocation<'a>>,
+{
+    let mut name = String::new();
+
+    while let Some(token) = tokens.peek() {
+        match &token.token {
+            Token::Dot => {
+                name.push('.');
+                tokens.next(); // Consume the dot
+            }
+            other => match identifier_or_keyword(other) {
+                Some(part) => {
+                    name.push_str(&part);
+                    tokens.next(); // Consume the identifier
+                }
+                None => break,
+            },
+        }
+    }
+
+    if name.is_empty() {
+        return Err(ParseError::MissingIdentifier(
+            "Expected extension name".to_string(),
+            start_location,
+        ));
+    }
+
+    Ok(name)
+}
+
+/// Returns the text of a token that can be used as a name inside option names
+/// and aggregate option values. Keywords such as `string`, `repeated` or `map`
+/// are valid there (e.g. `(buf.validate.field).string.min_len`).
+fn identifier_or_keyword(token: &Token) -> Option<String> {
+    match token {
+        Token::Identifier(s) => Some(s.to_string()),
+        Token::Syntax
+        | Token::Proto2
+        | Token::Proto3
+        | Token::Import
+        | Token::Package
+        | Token::Message
+        | Token::Enum
+        | Token::Service
+        | Token::Rpc
+        | Token::Returns
+        | Token::Option
+        | Token::Repeated
+        | Token::Oneof
+        | Token::Map
+        | Token::Reserved
+        | Token::To
+        | Token::Weak
+        | Token::Stream
+        | Token::Public
+        | Token::Extensions
+        | Token::Optional
+        | Token::Required
+        | Token::StringType => Some(token.to_string()),
+        _ => None,
+    }
+}
+
+/// Parses an option value from the token stream.
+///
+/// Besides scalar constants this accepts aggregate values written in protobuf
+/// text format, as used by custom options:
+///
+/// ```text
+/// option (google.api.http) = { get: "/v1/{id}" additional_bindings { get: "/v2/{id}" } };
+/// string name = 1 [(zod.field) = { min: 3, email: true }];
+/// ```
 fn parse_option_value<'a, I>(tokens: &mut Peekable<I>) -> Result<OptionValue, ParseError>
 where
     I: Iterator<Item = TokenWithLocation<'a>>,
 {
+    match tokens.peek() {
+        Some(TokenWithLocation {
+            token: Token::OpenBrace,
+            ..
+        }) => return Ok(OptionValue::Message(parse_message_literal(tokens)?)),
+        Some(TokenWithLocation {
+            token: Token::OpenBracket,
+            ..
+        }) => return parse_list_literal(tokens),
+        _ => {}
+    }
+
     let value_token = tokens
         .next()
         .ok_or_else(|| ParseError::UnexpectedEndOfInput(Location::new(0, 0)))?;
 
     match &value_token.token {
         Token::StringLiteral(s) => Ok(OptionValue::String(s.to_string())),
+        Token::Identifier("true") => Ok(OptionValue::Bool(true)),
+        Token::Identifier("false") => Ok(OptionValue::Bool(false)),
         Token::Identifier(s) => Ok(OptionValue::Identifier(s.to_string())),
         Token::DecimalIntLiteral(num) => Ok(OptionValue::DecimalInt(*num)),
         Token::OctalIntLiteral(num) => Ok(OptionValue::Octal(*num)),
@@ -1347,6 +1506,160 @@ where
     }
 }
 
+/// Parses a text-format message literal (`{ key: value, nested { ... } }`).
+///
+/// Entries may be separated by commas, semicolons or nothing at all, and the
+/// colon after a key is optional when the value is itself a message. Extension
+/// keys (`[foo.bar]: ...`) are kept with their brackets.
+fn parse_message_literal<'a, I>(
+    tokens: &mut Peekable<I>,
+) -> Result<Vec<(String, OptionValue)>, ParseError>
+where
+    I: Iterator<Item = TokenWithLocation<'a>>,
+{
+    let open_brace_token = tokens
+        .next()
+        .ok_or_else(|| ParseError::UnexpectedEndOfInput(Location::new(0, 0)))?
+        .expect(Token::OpenBrace)?;
+
+    let mut entries = Vec::new();
+
+    loop {
+        skip_comments_and_whitespace(tokens);
+
+        let key_token = tokens
+            .next()
+            .ok_or(ParseError::UnexpectedEndOfInput(open_brace_token.location))?;
+
+        let key = match &key_token.token {
+            Token::CloseBrace => break,
+            Token::Comma | Token::Semicolon => continue,
+            Token::OpenBracket => {
+                let name = parse_extension_name(tokens, key_token.location)?;
+                tokens
+                    .next()
+                    .ok_or(ParseError::UnexpectedEndOfInput(key_token.location))?
+                    .expect(Token::CloseBracket)?;
+                format!("[{}]", name)
+            }
+            other => identifier_or_keyword(other).ok_or_else(|| {
+                ParseError::UnexpectedToken(
+                    format!("Expected field name in message literal, found {:?}", other),
+                    key_token.location,
+                )
+            })?,
+        };
+
+        skip_comments_and_whitespace(tokens);
+
+        if let Some(TokenWithLocation {
+            token: Token::Colon,
+            ..
+        }) = tokens.peek()
+        {
+            tokens.next(); // Consume ':'
+            skip_comments_and_whitespace(tokens);
+        }
+
+        let value = parse_option_value(tokens)?;
+        entries.push((key, value));
+    }
+
+    Ok(entries)
+}
+
+/// Parses a list literal (`[1, 2, 3]` or `[{ ... }, { ... }]`) inside an option value.
+fn parse_list_literal<'a, I>(tokens: &mut Peekable<I>) -> Result<OptionValue, ParseError>
+where
+    I: Iterator<Item = TokenWithLocation<'a>>,
+{
+    let open_bracket_token = tokens
+        .next()
+        .ok_or_else(|| ParseError::UnexpectedEndOfInput(Location::new(0, 0)))?
+        .expect(Token::OpenBracket)?;
+
+    let mut values = Vec::new();
+
+    loop {
+        skip_comments_and_whitespace(tokens);
+
+        match tokens.peek() {
+            Some(TokenWithLocation {
+                token: Token::CloseBracket,
+                ..
+            }) => {
+                tokens.next(); // Consume ']'
+                break;
+            }
+            Some(TokenWithLocation {
+                token: Token::Comma,
+                ..
+            }) => {
+                tokens.next(); // Consume ','
+            }
+            Some(_) => values.push(parse_option_value(tokens)?),
+            None => return Err(ParseError::UnexpectedEndOfInput(open_bracket_token.location)),
+        }
+    }
+
+    Ok(OptionValue::List(values))
+}
+
+/// Parses the bracketed option list that may follow a field number,
+/// e.g. `[deprecated = true, (zod.field) = { min: 3 }]`.
+///
+/// Returns an empty list when the field has no options.
+fn parse_field_options<'a, I>(tokens: &mut Peekable<I>) -> Result<Vec<ProtoOption>, ParseError>
+where
+    I: Iterator<Item = TokenWithLocation<'a>>,
+{
+    let mut options = Vec::new();
+
+    let open_bracket_token = match tokens.peek() {
+        Some(TokenWithLocation {
+            token: Token::OpenBracket,
+            ..
+        }) => tokens.next().unwrap(), // Consume '['
+        _ => return Ok(options),
+    };
+
+    loop {
+        skip_comments_and_whitespace(tokens);
+
+        let name = parse_option_name(tokens)?;
+
+        tokens
+            .next()
+            .ok_or(ParseError::UnexpectedEndOfInput(open_bracket_token.location))?
+            .expect(Token::Equals)?;
+
+        let value = parse_option_value(tokens)?;
+        options.push(ProtoOption::new(name, value));
+
+        skip_comments_and_whitespace(tokens);
+
+        match tokens.next() {
+            Some(TokenWithLocation {
+                token: Token::Comma,
+                ..
+            }) => continue,
+            Some(TokenWithLocation {
+                token: Token::CloseBracket,
+                ..
+            }) => break,
+            Some(t) => {
+                return Err(ParseError::UnexpectedToken(
+                    format!("Expected ',' or ']', found {:?}", t.token),
+                    t.location,
+                ))
+            }
+            None => return Err(ParseError::UnexpectedEndOfInput(open_bracket_token.location)),
+        }
+    }
+
+    Ok(options)
+}
+
 fn parse_field_type(token: &TokenWithLocation) -> Result<FieldType, ParseError> {
     match &token.token {
         Token::Identifier(typ) => match *typ {
@@ -1374,6 +1687,67 @@ fn parse_field_type(token: &TokenWithLocation) -> Result<FieldType, ParseError>
     }
 }
 
+/// Parses a field type that may be a qualified message or enum name.
+///
+/// Accepts scalar types as well as names such as `Address`, `Outer.Inner`,
+/// `google.protobuf.Timestamp` and fully qualified `.pkg.Message`. Keywords
+/// are allowed as name components (e.g. `google.rpc.Status`).
+///
+/// # Arguments
+///
+/// * `tokens` - A mutable reference to a peekable iterator of TokenWithLocation.
+///
+/// # Returns
+///
+/// * `Result<FieldType, ParseError>` - The parsed field type on success, or a ParseError on failure.
+fn parse_qualified_field_type<'a, I>(tokens: &mut Peekable<I>) -> Result<FieldType, ParseError>
+where
+    I: Iterator<Item = TokenWithLocation<'a>>,
+{
+    let type_token = tokens
+        .next()
+        .ok_or_else(|| ParseError::UnexpectedEndOfInput(Location::new(0, 0)))?;
+
+    // A leading dot marks a fully qualified name
+    let mut type_name = if type_token.token == Token::Dot {
+        format!(".{}", parse_type_name_component(tokens, type_token.location)?)
+    } else {
+        match parse_field_type(&type_token)? {
+            FieldType::MessageOrEnum(name) => name,
+            scalar => return Ok(scalar),
+        }
+    };
+
+    while let Some(TokenWithLocation { token: Token::Dot, location }) = tokens.peek() {
+        let location = *location;
+        tokens.next(); // Consume the dot
+        type_name.push('.');
+        type_name.push_str(&parse_type_name_component(tokens, location)?);
+    }
+
+    Ok(FieldType::MessageOrEnum(type_name))
+}
+
+/// Parses one component of a qualified type name, which may be a keyword.
+fn parse_type_name_component<'a, I>(
+    tokens: &mut Peekable<I>,
+    location: Location,
+) -> Result<String, ParseError>
+where
+    I: Iterator<Item = TokenWithLocation<'a>>,
+{
+    let token = tokens
+        .next()
+        .ok_or(ParseError::UnexpectedEndOfInput(location))?;
+
+    identifier_or_keyword(&token.token).ok_or_else(|| {
+        ParseError::UnexpectedToken(
+            format!("Expected type name, found {:?}", token.token),
+            token.location,
+        )
+    })
+}
+
 fn parse_identifier<'a, I>(tokens: &mut Peekable<I>) -> Result<String, ParseError>
 where
     I: Iterator<Item = TokenWithLocation<'a>>,
@@ -1382,13 +1756,12 @@ where
         .next()
         .ok_or_else(|| ParseError::UnexpectedEndOfInput(Location::new(0, 0)))?;
 
-    match token.token {
-        Token::Identifier(s) => Ok(s.to_string()),
-        _ => Err(ParseError::UnexpectedToken(
+    identifier_or_keyword(&token.token).ok_or_else(|| {
+        ParseError::UnexpectedToken(
             format!("Expected identifier, found {:?}", token.token),
             token.location,
-        )),
-    }
+        )
+    })
 }
 
 fn parse_type<'a, I>(tokens: &mut Peekable<I>) -> Result<String, ParseError>
@@ -1396,7 +1769,6 @@ where
     I: Iterator<Item = TokenWithLocation<'a>>,
 {
     let mut type_name = String::new();
-    let mut first = true;
 
     loop {
         skip_comments_and_whitespace(tokens);
@@ -1406,22 +1778,13 @@ where
                 token: Token::Identifier(s),
                 ..
             }) => {
-                if !first {
-                    type_name.push('.');
-                }
                 type_name.push_str(s);
-                first = false;
                 tokens.next(); // Consume the identifier
             }
             Some(TokenWithLocation {
                 token: Token::Dot, ..
             }) => {
-                if first {
-                    return Err(ParseError::UnexpectedToken(
-                        "Unexpected dot at the beginning of type name".to_string(),
-                        tokens.peek().unwrap().location,
-                    ));
-                }
+                // Separates components; a leading dot marks a fully qualified name
                 type_name.push('.');
                 tokens.next(); // Consume the dot
             }
@@ -1429,9 +1792,6 @@ where
                 token: Token::Rpc, ..
             }) => {
                 // Special case: 'rpc' is part of the type name (e.g., google.rpc.Status)
-                if !first {
-                    type_name.push('.');
-                }
                 type_name.push_str("rpc");
                 tokens.next(); // Consume the 'rpc' token
             }
@@ -1490,10 +1850,7 @@ where
         .expect(Token::LessThan)?;
 
     // Parse key type
-    let key_type_token = tokens
-        .next()
-        .ok_or_else(|| ParseError::UnexpectedEndOfInput(Location::new(0, 0)))?;
-    let key_type = parse_field_type(&key_type_token)?;
+    let key_type = parse_qualified_field_type(tokens)?;
 
     // Expect ','
     tokens
@@ -1502,10 +1859,7 @@ where
         .expect(Token::Comma)?;
 
     // Parse value type
-    let value_type_token = tokens
-        .next()
-        .ok_or_else(|| ParseError::UnexpectedEndOfInput(Location::new(0, 0)))?;
-    let value_type = parse_field_type(&value_type_token)?;
+    let value_type = parse_qualified_field_type(tokens)?;
 
     // Expect '>'
     tokens
@@ -1827,8 +2181,8 @@ mod legacy_tests {
             } else {
                 assert_eq!(
                     field.label,
-                    FieldLabel::Optional,
-                    "Field {} should be optional",
+                    FieldLabel::Implicit,
+                    "Field {} should have implicit presence",
                     expected_name
                 );
             }
@@ -1842,6 +2196,6 @@ mod legacy_tests {
         assert_eq!(custom_field.name, "custom_field");
         assert_eq!(custom_field.typ, FieldType::String);
         assert_eq!(custom_field.number, NumberValue::DecimalInt(1));
-        assert_eq!(custom_field.label, FieldLabel::Optional);
+        assert_eq!(custom_field.label, FieldLabel::Implicit);
     }
 }
@@ -1,6 +1,6 @@
 #[cfg(test)]
 mod tests {
-    use crate::parser::{ast::{Enum, Field, FieldLabel, FieldType, Message, NumberValue, ProtoFile, Syntax}, parse_proto_file, ImportKind};
+    use crate::parser::{ast::{Enum, Field, FieldLabel, FieldType, Message, NumberValue, OptionValue, ProtoFile, Syntax}, parse_proto_file, ImportKind};
 
     #[test]
     fn test_parse_simple_proto() {
@@ -126,6 +126,28 @@ mod tests {
         assert_eq!(message.fields[1].typ, FieldType::Int32);
     }
 
+    #[test]
+    fn test_parse_field_labels() {
+        let proto_content = r#"
+            syntax = "proto3";
+            
+            message Labels {
+                string implicit = 1;
+                optional string explicit = 2;
+                required string mandatory = 3;
+                repeated string list = 4;
+            }
+        "#;
+
+        let proto = parse_proto_file(proto_content).unwrap();
+        let labels: Vec<&FieldLabel> = proto.messages[0].fields.iter().map(|f| &f.label).collect();
+        assert_eq!(
+            labels,
+            vec![&FieldLabel::Implicit, &FieldLabel::Optional, &FieldLabel::Required, &FieldLabel::Repeated]
+        );
+        assert_eq!(proto.messages[0].fields[1].typ, FieldType::String);
+    }
+
     #[test]
     fn test_parse_map_field() {
         let proto_content = r#"
@@ -320,4 +342,131 @@ mod tests {
         assert!(methods[3].client_streaming);
         assert!(methods[3].server_streaming);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_parse_service_qualified_types() {
+        let proto_content = r#"
+            syntax = "proto3";
+            
+            service HealthService {
+                rpc Check(google.protobuf.Empty) returns (.health.v1.Status);
+                rpc Fail(Empty) returns (google.rpc.Status);
+            }
+        "#;
+
+        let proto = parse_proto_file(proto_content).unwrap();
+        let methods = &proto.services[0].methods;
+        assert_eq!(methods[0].input_type, "google.protobuf.Empty");
+        assert_eq!(methods[0].output_type, ".health.v1.Status");
+        assert_eq!(methods[1].output_type, "google.rpc.Status");
+    }
+
+    #[test]
+    fn test_parse_field_options() {
+        let proto_content = r#"
+            syntax = "proto3";
+            
+            message WithFieldOptions {
+                option (zod.message) = { description: "Annotated" };
+                
+                string name = 1 [(zod.field) = { min: 3, array { max: 10 } }, deprecated = true];
+                string email = 2 [(buf.validate.field).string.email = true];
+                repeated string tags = 3 [(validate.rules).repeated = { min_items: 1, items { string { in: ["a", "b"] } } }];
+            }
+        "#;
+
+        let result = parse_proto_file(proto_content);
+        assert!(result.is_ok(), "Failed to parse proto file: {:?}", result.err());
+
+        let proto = result.unwrap();
+        let message = &proto.messages[0];
+        assert_eq!(message.options[0].name, "(zod.message)");
+
+        let name = &message.fields[0];
+        assert_eq!(name.options.len(), 2);
+        assert_eq!(name.options[0].name, "(zod.field)");
+        assert_eq!(
+            name.options[0].value,
+            OptionValue::Message(vec![
+                ("min".to_string(), OptionValue::DecimalInt(3)),
+                (
+                    "array".to_string(),
+                    OptionValue::Message(vec![("max".to_string(), OptionValue::DecimalInt(10))])
+                ),
+            ])
+        );
+        assert_eq!(name.options[1].name, "deprecated");
+        assert_eq!(name.options[1].value, OptionValue::Bool(true));
+
+        let email = &message.fields[1];
+        assert_eq!(email.options[0].name, "(buf.validate.field).string.email");
+
+        let tags = &message.fields[2];
+        assert_eq!(tags.label, FieldLabel::Repeated);
+        assert_eq!(tags.options[0].name, "(validate.rules).repeated");
+    }
+
+    #[test]
+    fn test_parse_oneof() {
+        let proto_content = r#"
+            syntax = "proto3";
+
+            message Contact {
+                string name = 1;
+                // How to reach the contact
+                oneof contact_info {
+                    option (my.oneof_option) = true;
+                    string email = 2;
+                    string phone = 3 [deprecated = true];
+                    Address address = 4;
+                }
+            }
+        "#;
+
+        let proto = parse_proto_file(proto_content).unwrap();
+        let message = &proto.messages[0];
+        assert_eq!(message.fields.len(), 1);
+        assert_eq!(message.oneofs.len(), 1);
+
+        let oneof = &message.oneofs[0];
+        assert_eq!(oneof.name, "contact_info");
+        assert_eq!(oneof.options.len(), 1);
+        let names: Vec<&str> = oneof.fields.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, vec!["email", "phone", "address"]);
+        assert_eq!(oneof.fields[2].typ, FieldType::MessageOrEnum("Address".to_string()));
+        assert_eq!(oneof.fields[1].options.len(), 1);
+    }
+
+    #[test]
+    fn test_parse_keywords_as_names() {
+        let proto_content = r#"
+            syntax = "proto2";
+
+            message Range {
+                required int64 to = 1;
+                optional bool optional = 2;
+                bool required = 3;
+                map<string, string> message = 4;
+                oneof stream {
+                    string service = 5;
+                }
+            }
+        "#;
+
+        let proto = parse_proto_file(proto_content).unwrap();
+        let message = &proto.messages[0];
+        let names: Vec<&str> = message.fields.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, vec!["to", "optional", "required", "message"]);
+        assert_eq!(message.fields[1].label, FieldLabel::Optional);
+        assert_eq!(message.fields[2].label, FieldLabel::Implicit);
+        assert_eq!(message.oneofs[0].name, "stream");
+        assert_eq!(message.oneofs[0].fields[0].name, "service");
+
+        // The bundled options use `optional` as a field name
+        let options = parse_proto_file(include_str!("../../proto/zod/options.proto")).unwrap();
+        let rules = options.messages.iter().find(|m| m.name == "FieldRules").unwrap();
+        let optional = rules.fields.iter().find(|f| f.name == "optional").unwrap();
+        assert_eq!(optional.label, FieldLabel::Optional);
+        assert_eq!(optional.typ, FieldType::Bool);
+    }
+}
@@ -40,6 +40,16 @@ pub trait Visitor {
             self.visit_field(field);
         }
         
+        // Visit oneof options and fields
+        for oneof in &message.oneofs {
+            for option in &oneof.options {
+                self.visit_option(option);
+            }
+            for field in &oneof.fields {
+                self.visit_field(field);
+            }
+        }
+        
         // Visit nested messages
         for nested_message in &message.nested_messages {
             self.visit_message(nested_message);
@@ -55,7 +65,7 @@ pub trait Visitor {
     fn visit_enum(&mut self, enum_def: &Enum) {
         // Visit enum options
         for option in &enum_def.options {
-            self.visit_enum_value_option(option);
+            self.visit_option(option);
         }
         
         // Visit all enum values
@@ -1,8 +1,10 @@
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
 use std::fs;
 use std::io;
-use crate::zod::generator::ImportStyle;
+use crate::zod::generator::{ImportStyle, ZodGeneratorConfig};
+use crate::zod::metadata::ZodMetadata;
 
 /// Configuration for the Zod schema generator
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -29,6 +31,10 @@ pub struct Config {
     /// Whether to override existing files
     #[serde(default = "default_true")]
     pub override_files: bool,
+    
+    /// Named annotation presets that fields reference with `@zod { preset: "<name>" }`
+    #[serde(default)]
+    pub presets: BTreeMap<String, ZodMetadata>,
 }
 
 /// Target language for schema generation
@@ -59,13 +65,314 @@ pub struct TypeScriptConfig {
     #[serde(default = "default_true")]
     pub single_file: bool,
     
-    /// Whether to generate type aliases
+    /// Whether to generate a TypeScript type for each schema
     #[serde(default = "default_true")]
     pub generate_types: bool,
     
+    /// How the TypeScript types are declared
+    #[serde(default)]
+    pub type_declarations: TypeDeclarations,
+    
+    /// Suffix of schema names (e.g. `Schema` for `UserSchema`); types keep the plain name
+    #[serde(default)]
+    pub schema_suffix: String,
+    
     /// Extension for generated files (default: .ts)
     #[serde(default = "default_ts_extension")]
     pub file_extension: String,
+    
+    /// Custom validators referenced by `custom` annotations, keyed by function name
+    #[serde(default)]
+    pub custom_validators: BTreeMap<String, CustomValidatorConfig>,
+    
+    /// Naming scheme for nested messages and enums
+    #[serde(default)]
+    pub nested_naming: NestedTypeNaming,
+    
+    /// Representation of oneofs
+    #[serde(default)]
+    pub oneof_style: OneofStyle,
+    
+    /// Representation of the protobuf well-known types
+    #[serde(default)]
+    pub well_known_types: WellKnownTypesConfig,
+    
+    /// Representation of 64-bit integers
+    #[serde(default)]
+    pub int64_strategy: Int64Strategy,
+    
+    /// Proto3 JSON mapping of field names, enums and bytes
+    #[serde(default)]
+    pub json_mapping: JsonMappingConfig,
+    
+    /// Representation of enums
+    #[serde(default)]
+    pub enum_style: EnumStyle,
+    
+    /// Accept unknown enum numbers, as proto3 enums are open
+    #[serde(default)]
+    pub open_enums: bool,
+    
+    /// Which fields may be omitted
+    #[serde(default)]
+    pub field_presence: PresenceStrategy,
+    
+    /// Module specifiers for the files of imported protos, keyed by proto path
+    /// prefix (e.g. `"common/": "@acme/common/"`); other imports are relative
+    #[serde(default)]
+    pub import_aliases: BTreeMap<String, String>,
+    
+    /// Generate a fetch-based Connect client for each service
+    #[serde(default)]
+    pub connect_client: bool,
+    
+    /// How objects treat unknown keys, unless a message annotation overrides it
+    #[serde(default)]
+    pub unknown_keys: UnknownKeys,
+    
+    /// Zod version to generate code for
+    #[serde(default)]
+    pub zod_version: ZodVersion,
+    
+    /// Parse proto3 JSON into domain values (`Date`, `bigint`, `Uint8Array`, enum
+    /// names) and generate encoders serialising them back
+    #[serde(default)]
+    pub domain_types: bool,
+    
+    /// Leave deprecated fields and enum values out of the schemas
+    #[serde(default)]
+    pub omit_deprecated: bool,
+    
+    /// Also describe schemas with their proto comments (`.describe()`), for
+    /// JSON Schema and OpenAPI exports
+    #[serde(default)]
+    pub describe_comments: bool,
+}
+
+/// A custom validator function that can be referenced from `custom` annotations
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CustomValidatorConfig {
+    /// Module specifier the function is imported from, e.g. `./validators`
+    pub import: String,
+    
+    /// How the function is applied to the schema
+    #[serde(default)]
+    pub refinement: RefinementKind,
+    
+    /// Default error message, used when the annotation does not provide one
+    #[serde(default)]
+    pub message: Option<String>,
+}
+
+/// How a custom validator is attached to a Zod schema
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum RefinementKind {
+    /// `.refine(fn, { message })` with `fn: (value) => boolean`
+    #[default]
+    Refine,
+    
+    /// `.superRefine(fn)` with `fn: (value, ctx) => void`
+    SuperRefine,
+}
+
+/// How nested messages and enums are named in generated code
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum NestedTypeNaming {
+    /// `ComplexMessage_Address`, as in protobuf-es and ts-proto
+    #[default]
+    Underscore,
+    
+    /// `ComplexMessage.Address`: nested schemas are attached to the parent
+    /// schema and their types to a namespace of the same name
+    Namespace,
+}
+
+/// How oneofs are represented in generated schemas
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum OneofStyle {
+    /// Members are optional keys of the message, as in the proto3 JSON
+    /// mapping, and a refinement rejects more than one being set
+    #[default]
+    OptionalKeys,
+    
+    /// The message is intersected with a union of `z.object` variants, each
+    /// setting at most one member
+    Union,
+    
+    /// A `{ case: "email", value }` discriminated union under the oneof name,
+    /// as in protobuf-es
+    DiscriminatedUnion,
+}
+
+/// Zod version the generated code is written for
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ZodVersion {
+    /// Zod 3 method chains, e.g. `z.string().email().describe("...")`
+    #[default]
+    V3,
+    
+    /// Zod 4, e.g. `z.email().meta({ description: "..." })`
+    V4,
+    
+    /// The functional `zod/mini` API, e.g. `z.optional(z.string().check(z.minLength(3)))`
+    Mini,
+}
+
+/// How the TypeScript type of each schema is declared
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum TypeDeclarations {
+    /// `type User = z.infer<typeof User>`
+    #[default]
+    Inferred,
+    
+    /// `interface User { ... }` written out, readable in IDE tooltips and usable without Zod
+    Explicit,
+}
+
+/// How generated objects treat keys that are not fields of the message
+///
+/// Written as `"strict"` or `{ "catchall": "z.string()" }`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum UnknownKeys {
+    /// Unknown keys are removed from the parsed value (Zod's default)
+    #[default]
+    Strip,
+    
+    /// Unknown keys are rejected, e.g. for inbound API payloads
+    Strict,
+    
+    /// Unknown keys are kept, e.g. for forward-compatible consumers
+    Passthrough,
+    
+    /// Unknown keys are kept and validated with the given Zod schema
+    Catchall(String),
+}
+
+/// Which fields may be omitted from generated objects
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum PresenceStrategy {
+    /// Every field except repeated and proto2 `required` fields is optional
+    #[default]
+    Partial,
+    
+    /// Only fields with explicit presence are optional: `optional` fields,
+    /// message fields and oneof members
+    Explicit,
+    
+    /// As `explicit`, but omitted fields without presence are filled in with
+    /// their zero value, as protobuf runtimes do when decoding
+    ZeroDefaults,
+}
+
+/// How enums are generated
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum EnumStyle {
+    /// `z.enum(['ACTIVE', 'INACTIVE'])` of the value names
+    #[default]
+    Names,
+    
+    /// A TypeScript `enum` with the numeric values, validated with `z.nativeEnum`
+    TsEnum,
+    
+    /// An `as const` object with the numeric values, validated with `z.nativeEnum`
+    ConstObject,
+    
+    /// A union accepting value names or numbers
+    Union,
+}
+
+/// Proto3 JSON mapping of generated schemas
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+pub struct JsonMappingConfig {
+    /// Key objects by JSON name (`json_name` or lowerCamelCase), accept enums
+    /// as names or numbers and validate `bytes` as base64
+    #[serde(default)]
+    pub enabled: bool,
+    
+    /// Also accept objects keyed by the proto field names
+    #[serde(default)]
+    pub accept_proto_names: bool,
+}
+
+/// How 64-bit integer fields are represented
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Int64Strategy {
+    /// Decimal strings, as in the proto3 JSON mapping, checked against the
+    /// range of the type
+    #[default]
+    String,
+    
+    /// `bigint` values
+    Bigint,
+    
+    /// Decimal strings or numbers, converted to `bigint`
+    Union,
+}
+
+/// How the well-known types are represented
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct WellKnownTypesConfig {
+    /// Representation of `google.protobuf.Timestamp`
+    #[serde(default)]
+    pub timestamp: TimestampMapping,
+    
+    /// Representation of the `*Value` wrapper types
+    #[serde(default)]
+    pub wrappers: WrapperMapping,
+    
+    /// Representation of `google.protobuf.Struct`, `Value` and `ListValue`
+    #[serde(default)]
+    pub r#struct: StructMapping,
+    
+    /// Zod expressions replacing the built-in mapping, keyed by full type
+    /// name, e.g. `{ "google.protobuf.Duration": "z.string()" }`
+    #[serde(default)]
+    pub overrides: BTreeMap<String, String>,
+}
+
+/// Representation of `google.protobuf.Timestamp`
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum TimestampMapping {
+    /// RFC 3339 string, e.g. `"2024-01-01T00:00:00Z"`
+    #[default]
+    String,
+    
+    /// RFC 3339 string converted to a `Date`
+    Date,
+}
+
+/// Representation of the `*Value` wrapper types
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum WrapperMapping {
+    /// The wrapped scalar or `null`
+    #[default]
+    Nullable,
+    
+    /// The wrapped scalar, `null` or `undefined`
+    Nullish,
+}
+
+/// Representation of `google.protobuf.Struct`, `Value` and `ListValue`
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum StructMapping {
+    /// Validated JSON values
+    #[default]
+    Json,
+    
+    /// `z.unknown()` for values, without validation
+    Unknown,
 }
 
 /// Python-specific configuration
@@ -110,7 +417,25 @@ impl Default for TypeScriptConfig {
             import_style: TsImportStyle::default(),
             single_file: default_true(),
             generate_types: default_true(),
+            type_declarations: TypeDeclarations::default(),
+            schema_suffix: String::new(),
             file_extension: default_ts_extension(),
+            custom_validators: BTreeMap::new(),
+            nested_naming: NestedTypeNaming::default(),
+            oneof_style: OneofStyle::default(),
+            well_known_types: WellKnownTypesConfig::default(),
+            int64_strategy: Int64Strategy::default(),
+            json_mapping: JsonMappingConfig::default(),
+            enum_style: EnumStyle::default(),
+            open_enums: false,
+            field_presence: PresenceStrategy::default(),
+            import_aliases: BTreeMap::new(),
+            connect_client: false,
+            unknown_keys: UnknownKeys::default(),
+            zod_version: ZodVersion::default(),
+            domain_types: false,
+            omit_deprecated: false,
+            describe_comments: false,
         }
     }
 }
@@ -134,6 +459,7 @@ impl Default for Config {
             python: PythonConfig::default(),
             create_dirs: default_true(),
             override_files: default_true(),
+            presets: BTreeMap::new(),
         }
     }
 }
@@ -149,6 +475,37 @@ impl From<TsImportStyle> for ImportStyle {
     }
 }
 
+/// Build the generator configuration from the TypeScript section of a config file
+impl From<&Config> for ZodGeneratorConfig {
+    fn from(config: &Config) -> Self {
+        ZodGeneratorConfig {
+            import_style: config.typescript.import_style.clone().into(),
+            single_file: config.typescript.single_file,
+            generate_types: config.typescript.generate_types,
+            type_declarations: config.typescript.type_declarations,
+            schema_suffix: config.typescript.schema_suffix.clone(),
+            output_dir: config.output_dir.clone(),
+            custom_validators: config.typescript.custom_validators.clone(),
+            presets: config.presets.clone(),
+            nested_naming: config.typescript.nested_naming,
+            oneof_style: config.typescript.oneof_style,
+            well_known_types: config.typescript.well_known_types.clone(),
+            int64_strategy: config.typescript.int64_strategy,
+            json_mapping: config.typescript.json_mapping,
+            enum_style: config.typescript.enum_style,
+            open_enums: config.typescript.open_enums,
+            field_presence: config.typescript.field_presence,
+            import_aliases: config.typescript.import_aliases.clone(),
+            connect_client: config.typescript.connect_client,
+            unknown_keys: config.typescript.unknown_keys.clone(),
+            zod_version: config.typescript.zod_version,
+            domain_types: config.typescript.domain_types,
+            omit_deprecated: config.typescript.omit_deprecated,
+            describe_comments: config.typescript.describe_comments,
+        }
+    }
+}
+
 // Default helper functions
 fn default_true() -> bool {
     true
@@ -0,0 +1,193 @@
+//! Service descriptors and a fetch-based client for the Connect protocol
+//!
+//! Unary methods use the [Connect](https://connectrpc.com/docs/protocol/)
+//! unary JSON protocol; server-streaming methods use the enveloped
+//! `application/connect+json` streaming protocol and are exposed as async
+//! iterables. Client- and bidi-streaming methods need full-duplex HTTP, which
+//! `fetch` does not offer, so they only appear in the descriptors.
+
+use crate::parser::ast::Method;
+use crate::zod::config::ZodVersion;
+
+/// Name of the module holding the client runtime in multi-file output
+pub const RUNTIME_MODULE: &str = "connect";
+
+/// Kind of a method, as in the `kind` of its descriptor
+pub fn method_kind(method: &Method) -> &'static str {
+    match (method.client_streaming, method.server_streaming) {
+        (false, false) => "unary",
+        (false, true) => "server_streaming",
+        (true, false) => "client_streaming",
+        (true, true) => "bidi_streaming",
+    }
+}
+
+/// Key of a method in descriptors and clients (`GetUser` -> `getUser`)
+pub fn method_key(name: &str) -> String {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(first) => first.to_lowercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// Client runtime: the error type, transport options and the calls used by
+/// the generated clients
+///
+/// Requests are validated with the input schema before they are sent, and
+/// responses with the output schema before they are returned. 64-bit integers
+/// held as `bigint` are sent as decimal strings, as in the proto3 JSON mapping.
+pub fn connect_runtime(version: ZodVersion) -> String {
+    format!("type ConnectSchema = {};\n\n{}", version.schema_type(), RUNTIME)
+}
+
+/// Client runtime, independent of the Zod version, laid out as Prettier formats it
+const RUNTIME: &str = concat!(
+    "export class ConnectError extends Error {\n",
+    "  readonly code: string;\n",
+    "\n",
+    "  constructor(code: string, message: string) {\n",
+    "    super(message);\n",
+    "    this.code = code;\n",
+    "    this.name = \"ConnectError\";\n",
+    "  }\n",
+    "}\n",
+    "\n",
+    "export interface ConnectMethod {\n",
+    "  name: string;\n",
+    "  input: ConnectSchema;\n",
+    "  output: ConnectSchema;\n",
+    "  kind: \"unary\" | \"server_streaming\" | \"client_streaming\" | \"bidi_streaming\";\n",
+    "}\n",
+    "\n",
+    "export interface ConnectTransportOptions {\n",
+    "  baseUrl: string;\n",
+    "  fetch?: typeof globalThis.fetch;\n",
+    "  headers?: Record<string, string>;\n",
+    "}\n",
+    "\n",
+    "interface ConnectCall {\n",
+    "  options: ConnectTransportOptions;\n",
+    "  service: { typeName: string };\n",
+    "  method: ConnectMethod;\n",
+    "  signal?: AbortSignal;\n",
+    "}\n",
+    "\n",
+    "const bigintToString = (_: string, item: unknown) =>\n",
+    "  typeof item === \"bigint\" ? item.toString() : item;\n",
+    "\n",
+    "const connectJson = (value: unknown) => JSON.stringify(value, bigintToString);\n",
+    "\n",
+    "const connectFetch = (\n",
+    "  call: ConnectCall,\n",
+    "  contentType: string,\n",
+    "  body: BodyInit,\n",
+    ") => {\n",
+    "  const baseUrl = call.options.baseUrl.replace(/\\/$/, \"\");\n",
+    "  const send = call.options.fetch ?? globalThis.fetch;\n",
+    "  return send(`${baseUrl}/${call.service.typeName}/${call.method.name}`, {\n",
+    "    method: \"POST\",\n",
+    "    headers: {\n",
+    "      ...call.options.headers,\n",
+    "      \"Content-Type\": contentType,\n",
+    "      \"Connect-Protocol-Version\": \"1\",\n",
+    "    },\n",
+    "    body,\n",
+    "    signal: call.signal,\n",
+    "  });\n",
+    "};\n",
+    "\n",
+    "const connectErrorOf = (json: any, fallback: string) =>\n",
+    "  new ConnectError(json?.code ?? \"unknown\", json?.message ?? fallback);\n",
+    "\n",
+    "export async function connectUnary<M extends ConnectMethod>(\n",
+    "  options: ConnectTransportOptions,\n",
+    "  service: { typeName: string },\n",
+    "  method: M,\n",
+    "  request: z.input<M[\"input\"]>,\n",
+    "  signal?: AbortSignal,\n",
+    "): Promise<z.infer<M[\"output\"]>> {\n",
+    "  const call = { options, service, method, signal };\n",
+    "  const body = connectJson(method.input.parse(request));\n",
+    "  const response = await connectFetch(call, \"application/json\", body);\n",
+    "  const json = await response.json().catch(() => null);\n",
+    "  if (!response.ok) throw connectErrorOf(json, response.statusText);\n",
+    "  return method.output.parse(json);\n",
+    "}\n",
+    "\n",
+    "export async function* connectServerStream<M extends ConnectMethod>(\n",
+    "  options: ConnectTransportOptions,\n",
+    "  service: { typeName: string },\n",
+    "  method: M,\n",
+    "  request: z.input<M[\"input\"]>,\n",
+    "  signal?: AbortSignal,\n",
+    "): AsyncGenerator<z.infer<M[\"output\"]>, void, undefined> {\n",
+    "  const call = { options, service, method, signal };\n",
+    "  // Messages are enveloped: one flags byte and a big-endian 32-bit length\n",
+    "  const body = connectJson(method.input.parse(request));\n",
+    "  const data = new TextEncoder().encode(body);\n",
+    "  const envelope = new Uint8Array(5 + data.length);\n",
+    "  new DataView(envelope.buffer).setUint32(1, data.length);\n",
+    "  envelope.set(data, 5);\n",
+    "  const contentType = \"application/connect+json\";\n",
+    "  const response = await connectFetch(call, contentType, envelope);\n",
+    "  if (!response.ok || !response.body) {\n",
+    "    const json = await response.json().catch(() => null);\n",
+    "    throw connectErrorOf(json, response.statusText);\n",
+    "  }\n",
+    "  const reader = response.body.getReader();\n",
+    "  let buffer = new Uint8Array(0);\n",
+    "  for (;;) {\n",
+    "    while (buffer.length >= 5) {\n",
+    "      const view = new DataView(buffer.buffer, buffer.byteOffset);\n",
+    "      const length = view.getUint32(1);\n",
+    "      if (buffer.length < 5 + length) break;\n",
+    "      const flags = buffer[0];\n",
+    "      const text = new TextDecoder().decode(buffer.subarray(5, 5 + length));\n",
+    "      const message = JSON.parse(text);\n",
+    "      buffer = buffer.subarray(5 + length);\n",
+    "      // The end-of-stream message carries the error, if any\n",
+    "      if (flags & 0x02) {\n",
+    "        if (message.error) {\n",
+    "          throw connectErrorOf(message.error, \"stream failed\");\n",
+    "        }\n",
+    "        return;\n",
+    "      }\n",
+    "      yield method.output.parse(message);\n",
+    "    }\n",
+    "    const { done, value } = await reader.read();\n",
+    "    if (done) {\n",
+    "      throw new ConnectError(\n",
+    "        \"unknown\",\n",
+    "        \"stream ended without an end-of-stream message\",\n",
+    "      );\n",
+    "    }\n",
+    "    const next = new Uint8Array(buffer.length + value.length);\n",
+    "    next.set(buffer);\n",
+    "    next.set(value, buffer.length);\n",
+    "    buffer = next;\n",
+    "  }\n",
+    "}",
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_method_names() {
+        assert_eq!(method_key("GetUser"), "getUser");
+        assert_eq!(method_key("listURLs"), "listURLs");
+
+        let method = Method {
+            name: "Watch".to_string(),
+            input_type: "WatchRequest".to_string(),
+            output_type: "Event".to_string(),
+            client_streaming: false,
+            server_streaming: true,
+            options: Vec::new(),
+        };
+        assert_eq!(method_kind(&method), "server_streaming");
+        assert!(connect_runtime(ZodVersion::V3).contains("export async function* connectServerStream<"));
+    }
+}
@@ -0,0 +1,308 @@
+//! Declaration order of generated schemas
+//!
+//! Schemas are `const` declarations, so a schema must be declared before any
+//! schema that uses it eagerly. The messages and enums of a file, including
+//! nested ones, form a graph whose edges are field references (and, so that
+//! nested types come first, parent-to-nested edges). Its strongly connected
+//! components give both the output order and the recursive types: references
+//! between members of a cycle are wrapped in `z.lazy`.
+
+use crate::parser::ast::{Enum, FieldType, Message, ProtoFile};
+use crate::zod::registry::{qualified_name, TypeRegistry};
+use std::collections::{HashMap, HashSet};
+
+/// A message or enum to generate
+#[derive(Debug, Clone, Copy)]
+pub enum DeclarationKind<'a> {
+    Message(&'a Message),
+    Enum(&'a Enum),
+}
+
+/// A message or enum together with its position in the file
+#[derive(Debug, Clone)]
+pub struct Declaration<'a> {
+    /// Name relative to the package, e.g. `ComplexMessage.Address`
+    pub full_name: String,
+
+    /// Dotted name of the enclosing message, empty for top-level types
+    pub scope: String,
+
+    pub kind: DeclarationKind<'a>,
+}
+
+/// The declarations of a file in dependency order
+#[derive(Debug, Clone)]
+pub struct DependencyGraph<'a> {
+    /// Declarations, each after the ones it depends on
+    declarations: Vec<Declaration<'a>>,
+
+    /// Component index of each declaration, keyed by full name
+    components: HashMap<String, usize>,
+
+    /// Components that contain a cycle
+    recursive: HashSet<usize>,
+
+    /// Full names of the types each declaration uses, keyed by full name
+    references: HashMap<String, Vec<String>>,
+}
+
+impl<'a> DependencyGraph<'a> {
+    /// Order the messages and enums of a proto file
+    pub fn new(proto_file: &'a ProtoFile, registry: &TypeRegistry) -> Self {
+        // Declarations in source order: top-level enums, then each message
+        // preceded by its nested types
+        let mut declarations = Vec::new();
+        for enum_def in &proto_file.enums {
+            declarations.push(Declaration {
+                full_name: enum_def.name.clone(),
+                scope: String::new(),
+                kind: DeclarationKind::Enum(enum_def),
+            });
+        }
+        for message in &proto_file.messages {
+            collect_message(message, "", &mut declarations);
+        }
+
+        let index: HashMap<&str, usize> = declarations
+            .iter()
+            .enumerate()
+            .map(|(i, declaration)| (declaration.full_name.as_str(), i))
+            .collect();
+
+        let references: HashMap<String, Vec<String>> = declarations
+            .iter()
+            .map(|declaration| {
+                let names = match declaration.kind {
+                    DeclarationKind::Enum(_) => Vec::new(),
+                    DeclarationKind::Message(message) => {
+                        dependencies(message, &declaration.full_name, registry)
+                    }
+                };
+                (declaration.full_name.clone(), names)
+            })
+            .collect();
+
+        let edges: Vec<Vec<usize>> = declarations
+            .iter()
+            .map(|declaration| {
+                references[&declaration.full_name]
+                    .iter()
+                    .filter_map(|name| index.get(name.as_str()).copied())
+                    .collect()
+            })
+            .collect();
+
+        let sccs = strongly_connected_components(&edges);
+
+        let mut components = HashMap::new();
+        let mut recursive = HashSet::new();
+        let mut order = Vec::new();
+        for (component, members) in sccs.iter().enumerate() {
+            let is_cycle = members.len() > 1 || edges[members[0]].contains(&members[0]);
+            if is_cycle {
+                recursive.insert(component);
+            }
+            for &member in members {
+                components.insert(declarations[member].full_name.clone(), component);
+                order.push(member);
+            }
+        }
+
+        let mut slots: Vec<Option<Declaration<'a>>> = declarations.into_iter().map(Some).collect();
+        let declarations = order
+            .into_iter()
+            .map(|i| slots[i].take().unwrap())
+            .collect();
+
+        DependencyGraph {
+            declarations,
+            components,
+            recursive,
+            references,
+        }
+    }
+
+    /// Declarations in the order they must be generated
+    pub fn declarations(&self) -> &[Declaration<'a>] {
+        &self.declarations
+    }
+
+    /// Full names of the declared types a declaration uses, including its nested types
+    pub fn references(&self, full_name: &str) -> &[String] {
+        self.references.get(full_name).map_or(&[], Vec::as_slice)
+    }
+
+    /// Whether the type is part of a reference cycle
+    pub fn is_recursive(&self, full_name: &str) -> bool {
+        self.components
+            .get(full_name)
+            .is_some_and(|component| self.recursive.contains(component))
+    }
+
+    /// Whether a reference from `from` to `to` must be deferred with `z.lazy`
+    pub fn needs_lazy(&self, from: &str, to: &str) -> bool {
+        match (self.components.get(from), self.components.get(to)) {
+            (Some(from), Some(to)) => from == to && self.recursive.contains(from),
+            _ => false,
+        }
+    }
+}
+
+/// Append a message and its nested types, nested types first
+fn collect_message<'a>(message: &'a Message, scope: &str, declarations: &mut Vec<Declaration<'a>>) {
+    let full_name = qualified_name(scope, &message.name);
+
+    for enum_def in &message.nested_enums {
+        declarations.push(Declaration {
+            full_name: qualified_name(&full_name, &enum_def.name),
+            scope: full_name.clone(),
+            kind: DeclarationKind::Enum(enum_def),
+        });
+    }
+    for nested in &message.nested_messages {
+        collect_message(nested, &full_name, declarations);
+    }
+
+    declarations.push(Declaration {
+        full_name,
+        scope: scope.to_string(),
+        kind: DeclarationKind::Message(message),
+    });
+}
+
+/// Full names of the types a message depends on, in field order
+fn dependencies(message: &Message, full_name: &str, registry: &TypeRegistry) -> Vec<String> {
+    let mut names: Vec<String> = message
+        .nested_enums
+        .iter()
+        .map(|e| &e.name)
+        .chain(message.nested_messages.iter().map(|m| &m.name))
+        .map(|name| qualified_name(full_name, name))
+        .collect();
+
+    let oneof_fields = message.oneofs.iter().flat_map(|oneof| &oneof.fields);
+    for field in message.fields.iter().chain(oneof_fields) {
+        let types = match field.typ {
+            FieldType::Map(ref key_type, ref value_type) => vec![key_type.as_ref(), value_type.as_ref()],
+            ref typ => vec![typ],
+        };
+        for typ in types {
+            if let FieldType::MessageOrEnum(type_name) = typ {
+                names.extend(registry.resolve(full_name, type_name));
+            }
+        }
+    }
+
+    names
+}
+
+/// Tarjan's algorithm
+///
+/// Components are returned in reverse topological order, i.e. every
+/// component comes after the components it has edges to. Members of a
+/// component are sorted by index.
+fn strongly_connected_components(edges: &[Vec<usize>]) -> Vec<Vec<usize>> {
+    struct State<'e> {
+        edges: &'e [Vec<usize>],
+        next_index: usize,
+        index: Vec<Option<usize>>,
+        low_link: Vec<usize>,
+        stack: Vec<usize>,
+        on_stack: Vec<bool>,
+        components: Vec<Vec<usize>>,
+    }
+
+    fn visit(state: &mut State, node: usize) {
+        state.index[node] = Some(state.next_index);
+        state.low_link[node] = state.next_index;
+        state.next_index += 1;
+        state.stack.push(node);
+        state.on_stack[node] = true;
+
+        for &next in &state.edges[node] {
+            match state.index[next] {
+                None => {
+                    visit(state, next);
+                    state.low_link[node] = state.low_link[node].min(state.low_link[next]);
+                }
+                Some(next_index) if state.on_stack[next] => {
+                    state.low_link[node] = state.low_link[node].min(next_index);
+                }
+                Some(_) => {}
+            }
+        }
+
+        if Some(state.low_link[node]) == state.index[node] {
+            let mut component = Vec::new();
+            while let Some(member) = state.stack.pop() {
+                state.on_stack[member] = false;
+                component.push(member);
+                if member == node {
+                    break;
+                }
+            }
+            component.sort_unstable();
+            state.components.push(component);
+        }
+    }
+
+    let mut state = State {
+        edges,
+        next_index: 0,
+        index: vec![None; edges.len()],
+        low_link: vec![0; edges.len()],
+        stack: Vec::new(),
+        on_stack: vec![false; edges.len()],
+        components: Vec::new(),
+    };
+
+    for node in 0..edges.len() {
+        if state.index[node].is_none() {
+            visit(&mut state, node);
+        }
+    }
+
+    state.components
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_proto_file;
+
+    #[test]
+    fn test_dependency_order_and_cycles() {
+        let proto_file = parse_proto_file(
+            r#"
+            syntax = "proto3";
+
+            message User {
+                Address address = 1;
+                Status status = 2;
+            }
+            message Address { string street = 1; }
+            message TreeNode { repeated TreeNode children = 1; }
+            message Post { repeated Comment comments = 1; }
+            message Comment { Post post = 1; }
+            enum Status { ACTIVE = 0; }
+            "#,
+        )
+        .unwrap();
+        let registry = TypeRegistry::new(&proto_file);
+        let graph = DependencyGraph::new(&proto_file, &registry);
+
+        let order: Vec<&str> = graph
+            .declarations()
+            .iter()
+            .map(|declaration| declaration.full_name.as_str())
+            .collect();
+        assert_eq!(order, vec!["Status", "Address", "User", "TreeNode", "Post", "Comment"]);
+
+        assert!(!graph.is_recursive("User"));
+        assert!(graph.is_recursive("TreeNode"));
+        assert!(graph.needs_lazy("TreeNode", "TreeNode"));
+        assert!(graph.needs_lazy("Post", "Comment"));
+        assert!(graph.needs_lazy("Comment", "Post"));
+        assert!(!graph.needs_lazy("User", "Address"));
+    }
+}
@@ -0,0 +1,85 @@
+//! Domain schemas converting proto3 JSON to and from idiomatic TypeScript values
+//!
+//! With domain types enabled, each message and enum gets two schemas: one
+//! parsing the wire form (proto3 JSON) into domain values, with `Timestamp`
+//! as `Date`, 64-bit integers as `bigint`, `bytes` as `Uint8Array` and enums
+//! as names, and an encoder (`UserToWire`) validating domain values and
+//! serialising them back to the wire form.
+
+use crate::parser::ast::{FieldType, Message};
+
+/// Suffix of the encoder schemas, e.g. `UserToWire`
+pub const ENCODER_SUFFIX: &str = "ToWire";
+
+/// Name of the helper function decoding base64 `bytes` to a `Uint8Array`
+pub const BYTES_FROM_BASE64: &str = "protoBytesFromBase64";
+
+/// Name of the helper function encoding a `Uint8Array` as base64
+pub const BYTES_TO_BASE64: &str = "protoBytesToBase64";
+
+/// Form of the data a schema is generated for
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Direction {
+    /// Validates the wire form and outputs it unchanged
+    Wire,
+
+    /// Parses the wire form into domain values
+    Decode,
+
+    /// Validates domain values and serialises them to the wire form
+    Encode,
+}
+
+/// Whether any field of the messages, including nested ones, is `bytes`
+pub fn uses_bytes(messages: &[Message]) -> bool {
+    messages.iter().any(|message| {
+        let oneof_fields = message.oneofs.iter().flat_map(|oneof| &oneof.fields);
+        let uses = message.fields.iter().chain(oneof_fields).any(|field| match field.typ {
+            FieldType::Bytes => true,
+            FieldType::Map(_, ref value_type) => **value_type == FieldType::Bytes,
+            _ => false,
+        });
+        uses || uses_bytes(&message.nested_messages)
+    })
+}
+
+/// Helper functions converting `bytes` between base64 (standard or URL-safe) and `Uint8Array`
+pub fn bytes_helpers() -> String {
+    format!(
+        concat!(
+            "const {0} = (value: string) => {{\n",
+            "  const binary = atob(value.replace(/-/g, \"+\").replace(/_/g, \"/\"));\n",
+            "  return Uint8Array.from(binary, (c) => c.charCodeAt(0));\n",
+            "}};\n",
+            "const {1} = (value: Uint8Array) => {{\n",
+            "  const binary = Array.from(value, (byte) => String.fromCharCode(byte));\n",
+            "  return btoa(binary.join(\"\"));\n",
+            "}};",
+        ),
+        BYTES_FROM_BASE64, BYTES_TO_BASE64
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_proto_file;
+
+    #[test]
+    fn test_uses_bytes() {
+        let proto_file = parse_proto_file(
+            r#"
+            syntax = "proto3";
+            message Upload {
+                message Chunk { bytes data = 1; }
+                repeated Chunk chunks = 1;
+            }
+            message Ping { string id = 1; }
+            "#,
+        )
+        .unwrap();
+        assert!(uses_bytes(&proto_file.messages));
+        assert!(!uses_bytes(&proto_file.messages[1..]));
+        assert!(bytes_helpers().starts_with("const protoBytesFromBase64 = "));
+    }
+}
@@ -1,6 +1,27 @@
-use crate::parser::ast::{Enum, Field, FieldLabel, FieldType, Message, ProtoFile};
-use crate::zod::metadata::{ZodFileMetadata, ZodMetadata};
-use std::collections::HashMap;
+use crate::parser::ast::{
+    Enum, EnumValue, Field, FieldLabel, FieldType, Message, OneOf, OptionValue, ProtoFile, ProtoOption, Service,
+};
+use crate::zod::config::{
+    CustomValidatorConfig, EnumStyle, Int64Strategy, JsonMappingConfig, NestedTypeNaming,
+    PresenceStrategy, OneofStyle, RefinementKind, TypeDeclarations, UnknownKeys, WellKnownTypesConfig,
+    ZodVersion,
+};
+use crate::zod::metadata::{CrossFieldRule, ZodFileMetadata, ZodMetadata};
+use crate::zod::dependencies::{Declaration, DeclarationKind, DependencyGraph};
+use crate::zod::registry::{identifier, qualified_name, TypeKind, TypeRegistry};
+use crate::zod::well_known::{is_timestamp, json_value_schema, WellKnownSchema};
+use crate::zod::connect::{connect_runtime, method_key, method_kind, RUNTIME_MODULE};
+use crate::zod::domain::{bytes_helpers, uses_bytes, Direction, BYTES_FROM_BASE64, BYTES_TO_BASE64, ENCODER_SUFFIX};
+use crate::zod::jsdoc::{is_deprecated, is_deprecated_value, jsdoc, DeclarationDocs};
+use crate::zod::layout::layout;
+use crate::zod::imports::{module_path, relative_module, ImportedFile, ImportedType, ImportedTypes};
+use crate::zod::json_mapping::{
+    base64, enum_number, has_renamed_fields, json_name, lower_camel_case, proto_name_aliases_helper,
+    renamed_fields, BASE64_PATTERN, PROTO_NAME_ALIASES, PROTO_NAMES_MODULE,
+};
+use log::warn;
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::fmt::Write;
 
 /// Generator for Zod schemas from Protocol Buffer definitions
@@ -14,8 +35,114 @@ pub struct ZodGenerator {
     /// Whether to generate a single file or multiple files
     single_file: bool,
     
+    /// Whether to generate a TypeScript type for each schema
+    generate_types: bool,
+    
+    /// How the TypeScript types are declared
+    type_declarations: TypeDeclarations,
+    
+    /// Suffix of schema names; types keep the plain name
+    schema_suffix: String,
+    
     /// Output directory for generated files
     output_dir: String,
+    
+    /// Custom validators available to `custom` annotations, keyed by function name
+    custom_validators: BTreeMap<String, CustomValidatorConfig>,
+    
+    /// Naming scheme for nested messages and enums
+    nested_naming: NestedTypeNaming,
+    
+    /// Representation of oneofs
+    oneof_style: OneofStyle,
+    
+    /// Representation of the protobuf well-known types
+    well_known_types: WellKnownTypesConfig,
+    
+    /// Representation of 64-bit integers
+    int64_strategy: Int64Strategy,
+    
+    /// Proto3 JSON mapping of field names, enums and bytes
+    json_mapping: JsonMappingConfig,
+    
+    /// Representation of enums
+    enum_style: EnumStyle,
+    
+    /// Whether enums accept unknown numbers
+    open_enums: bool,
+    
+    /// Which fields may be omitted
+    field_presence: PresenceStrategy,
+    
+    /// Module specifiers replacing relative imports, keyed by proto path prefix
+    import_aliases: BTreeMap<String, String>,
+    
+    /// Whether to generate a fetch-based Connect client for each service
+    connect_client: bool,
+    
+    /// How objects treat unknown keys, unless a message annotation overrides it
+    unknown_keys: UnknownKeys,
+    
+    /// Zod version to generate code for
+    zod_version: ZodVersion,
+    
+    /// Whether schemas parse into domain values, with encoders serialising them back
+    domain_types: bool,
+    
+    /// Whether deprecated fields and enum values are left out
+    omit_deprecated: bool,
+    
+    /// Whether proto comments also describe the schemas
+    describe_comments: bool,
+    
+    /// Import path of the proto file being generated, which names the output file
+    source_path: Option<String>,
+    
+    /// Types declared in the files imported by the proto file
+    imports: ImportedTypes,
+}
+
+/// How the absence of a field is handled
+enum Presence {
+    /// The field must be set
+    Required,
+    
+    /// The field may be omitted
+    Optional,
+    
+    /// An omitted field takes the given default value: its zero value or a declared default
+    Default(String),
+}
+
+/// Types visible while generating a message
+#[derive(Clone, Copy)]
+struct TypeScope<'a> {
+    /// Dotted name of the message being generated
+    name: &'a str,
+    
+    /// Types declared in the file
+    registry: &'a TypeRegistry,
+    
+    /// Declaration order and reference cycles
+    graph: &'a DependencyGraph<'a>,
+    
+    /// Types declared in imported files
+    imports: &'a ImportedTypes,
+    
+    /// Form of the data the schema is generated for
+    direction: Direction,
+}
+
+impl TypeScope<'_> {
+    /// Resolve a type reference to a package-relative name
+    fn resolve(&self, type_name: &str) -> Option<String> {
+        self.registry.resolve(self.name, type_name)
+    }
+    
+    /// Resolve a type reference not declared in the file to an imported type
+    fn resolve_import(&self, type_name: &str) -> Option<&ImportedType> {
+        self.imports.resolve(self.registry.package(), self.name, type_name)
+    }
 }
 
 /// Supported import styles for Zod
@@ -38,8 +165,79 @@ pub struct ZodGeneratorConfig {
     /// Whether to generate a single file or multiple files
     pub single_file: bool,
     
+    /// Whether to generate a TypeScript type for each schema
+    ///
+    /// Types of recursive messages are always generated, as their schemas need them.
+    pub generate_types: bool,
+    
+    /// How the TypeScript types are declared: inferred from the schemas or written out
+    pub type_declarations: TypeDeclarations,
+    
+    /// Suffix of schema names, e.g. `Schema` for `UserSchema`; types keep the plain name
+    pub schema_suffix: String,
+    
     /// Output directory for generated files
     pub output_dir: String,
+    
+    /// Custom validators available to `custom` annotations, keyed by function name
+    pub custom_validators: BTreeMap<String, CustomValidatorConfig>,
+    
+    /// Annotation presets available to `preset` annotations, keyed by name
+    ///
+    /// Presets defined in a proto file with `// @zod-preset:` override these.
+    pub presets: BTreeMap<String, ZodMetadata>,
+    
+    /// Naming scheme for nested messages and enums
+    pub nested_naming: NestedTypeNaming,
+    
+    /// Representation of oneofs
+    pub oneof_style: OneofStyle,
+    
+    /// Representation of the protobuf well-known types
+    pub well_known_types: WellKnownTypesConfig,
+    
+    /// Representation of 64-bit integers
+    pub int64_strategy: Int64Strategy,
+    
+    /// Proto3 JSON mapping of field names, enums and bytes
+    pub json_mapping: JsonMappingConfig,
+    
+    /// Representation of enums
+    pub enum_style: EnumStyle,
+    
+    /// Accept unknown enum numbers, as proto3 enums are open
+    pub open_enums: bool,
+    
+    /// Which fields may be omitted
+    pub field_presence: PresenceStrategy,
+    
+    /// Module specifiers replacing relative imports, keyed by proto path prefix
+    ///
+    /// E.g. `"common/": "@acme/common/"` imports the types of `common/money.proto`
+    /// from `@acme/common/money`.
+    pub import_aliases: BTreeMap<String, String>,
+    
+    /// Generate a fetch-based Connect client for each service, besides its
+    /// method descriptors
+    pub connect_client: bool,
+    
+    /// How objects treat unknown keys, unless a message annotation overrides it
+    pub unknown_keys: UnknownKeys,
+    
+    /// Zod version to generate code for: Zod 3, Zod 4 or `zod/mini`
+    pub zod_version: ZodVersion,
+    
+    /// Parse proto3 JSON into domain values (`Date`, `bigint`, `Uint8Array`, enum
+    /// names), and generate an encoder (`UserToWire`) serialising them back
+    pub domain_types: bool,
+    
+    /// Leave deprecated fields and enum values out of the schemas and types, so
+    /// that strict schemas reject them
+    pub omit_deprecated: bool,
+    
+    /// Describe schemas without a `description` annotation with their proto
+    /// comment, which then reaches JSON Schema and OpenAPI exports
+    pub describe_comments: bool,
 }
 
 impl Default for ZodGeneratorConfig {
@@ -47,275 +245,2060 @@ impl Default for ZodGeneratorConfig {
         ZodGeneratorConfig {
             import_style: ImportStyle::Named,
             single_file: true,
+            generate_types: true,
+            type_declarations: TypeDeclarations::default(),
+            schema_suffix: String::new(),
             output_dir: "generated".to_string(),
+            custom_validators: BTreeMap::new(),
+            presets: BTreeMap::new(),
+            nested_naming: NestedTypeNaming::default(),
+            oneof_style: OneofStyle::default(),
+            well_known_types: WellKnownTypesConfig::default(),
+            int64_strategy: Int64Strategy::default(),
+            json_mapping: JsonMappingConfig::default(),
+            enum_style: EnumStyle::default(),
+            open_enums: false,
+            field_presence: PresenceStrategy::default(),
+            import_aliases: BTreeMap::new(),
+            connect_client: false,
+            unknown_keys: UnknownKeys::default(),
+            zod_version: ZodVersion::default(),
+            domain_types: false,
+            omit_deprecated: false,
+            describe_comments: false,
         }
     }
 }
 
 impl ZodGenerator {
     /// Create a new ZodGenerator with the given metadata and configuration
-    pub fn new(metadata: ZodFileMetadata, config: ZodGeneratorConfig) -> Self {
+    pub fn new(mut metadata: ZodFileMetadata, config: ZodGeneratorConfig) -> Self {
+        // Expand `preset` references; file-level presets override configured ones
+        let mut presets: HashMap<String, ZodMetadata> = config.presets.into_iter().collect();
+        presets.extend(metadata.presets.clone());
+        metadata.resolve_presets(&presets);
+        
         ZodGenerator {
             metadata,
             import_style: config.import_style,
             single_file: config.single_file,
+            generate_types: config.generate_types,
+            type_declarations: config.type_declarations,
+            schema_suffix: config.schema_suffix,
             output_dir: config.output_dir,
+            custom_validators: config.custom_validators,
+            nested_naming: config.nested_naming,
+            oneof_style: config.oneof_style,
+            well_known_types: config.well_known_types,
+            int64_strategy: config.int64_strategy,
+            json_mapping: config.json_mapping,
+            enum_style: config.enum_style,
+            open_enums: config.open_enums,
+            field_presence: config.field_presence,
+            import_aliases: config.import_aliases,
+            connect_client: config.connect_client,
+            unknown_keys: config.unknown_keys,
+            zod_version: config.zod_version,
+            domain_types: config.domain_types,
+            omit_deprecated: config.omit_deprecated,
+            describe_comments: config.describe_comments,
+            source_path: None,
+            imports: ImportedTypes::default(),
         }
     }
     
+    /// Generate the proto file at `source_path` (its import path, e.g.
+    /// `shop/v1/order.proto`), with the files it imports
+    ///
+    /// Output files are then named after the proto file instead of its package,
+    /// and types from the imported files are imported from their generated modules.
+    pub fn with_imports(mut self, source_path: &str, imported_files: &[ImportedFile]) -> Self {
+        self.source_path = Some(source_path.to_string());
+        self.imports = ImportedTypes::new(imported_files);
+        self
+    }
+    
     /// Generate Zod schemas for a Protocol Buffer file
-    pub fn generate(&self, proto_file: &ProtoFile) -> HashMap<String, String> {
-        let mut generated_files = HashMap::new();
-        
-        if self.single_file {
+    ///
+    /// Files are keyed by their path, in order, and laid out the way Prettier
+    /// formats them with its defaults, so regenerating an unchanged proto file
+    /// gives byte-for-byte the same output.
+    pub fn generate(&self, proto_file: &ProtoFile) -> BTreeMap<String, String> {
+        let generated_files = if self.single_file {
             let file_content = self.generate_single_file(proto_file);
             let filename = self.get_output_filename(proto_file);
-            generated_files.insert(filename, file_content);
+            BTreeMap::from([(filename, file_content)])
         } else {
-            // Generate separate files for each message and enum
-            // This would be implemented for multi-file output
-            unimplemented!("Multi-file generation not yet implemented");
-        }
+            self.generate_multi_file(proto_file)
+        };
         
-        generated_files
+        generated_files.into_iter()
+            .map(|(filename, content)| (filename, self.finish_file(&content)))
+            .collect()
+    }
+    
+    /// Lay out a generated file and prepend its provenance header
+    ///
+    /// The header records the proto file and generator version the file comes
+    /// from, and a hash of the rest of the file to detect edits and stale output.
+    fn finish_file(&self, content: &str) -> String {
+        let body = layout(content);
+        let source = self.source_path.as_ref()
+            .map(|path| format!(" from {}", path))
+            .unwrap_or_default();
+        format!(
+            "// Generated by protobuf_to_zod v{}{}. Do not edit.\n// Content hash: {:016x}\n\n{}",
+            env!("CARGO_PKG_VERSION"),
+            source,
+            content_hash(&body),
+            body,
+        )
     }
     
     /// Generate a single TypeScript file with all Zod schemas
     fn generate_single_file(&self, proto_file: &ProtoFile) -> String {
         let mut content = String::new();
+        let registry = TypeRegistry::new(proto_file);
+        let graph = DependencyGraph::new(proto_file, &registry);
+        
+        // The file sits next to the directory of a multi-file output
+        let directory = self.get_output_directory(proto_file);
+        let from_dir = match self.source_path {
+            Some(_) => directory.rsplit_once('/').map_or("", |(parent, _)| parent),
+            None => "",
+        };
         
         // Add imports
         writeln!(content, "{}", self.generate_imports()).unwrap();
+        for import in self.generate_custom_validator_imports(None, from_dir) {
+            writeln!(content, "{}", import).unwrap();
+        }
+        let messages: Vec<&Message> = proto_file.messages.iter().collect();
+        let services: Vec<&Service> = proto_file.services.iter().collect();
+        for import in self.generate_type_imports(&messages, &services, &registry, &graph, from_dir) {
+            writeln!(content, "{}", import).unwrap();
+        }
         writeln!(content).unwrap();
         
+        self.write_preamble(&mut content, &proto_file.messages, true);
+        
+        // Generate enums and messages, each after the types it uses
+        for declaration in graph.declarations() {
+            writeln!(content, "{}", self.generate_declaration(declaration, &registry, &graph)).unwrap();
+            writeln!(content).unwrap();
+        }
+        
+        // Services use the messages, so they come last
+        if self.connect_client && !services.is_empty() {
+            writeln!(content, "{}", connect_runtime(self.zod_version)).unwrap();
+            writeln!(content).unwrap();
+        }
+        let scope = TypeScope {
+            name: "",
+            registry: &registry,
+            graph: &graph,
+            imports: &self.imports,
+            direction: self.parse_direction(),
+        };
+        for service in &services {
+            writeln!(content, "{}", self.generate_service(service, &scope)).unwrap();
+            writeln!(content).unwrap();
+        }
+        
+        content
+    }
+    
+    /// Generate one TypeScript file per top-level message or enum, and an
+    /// `index.ts` re-exporting all of them
+    ///
+    /// Nested types are generated in the file of their top-level message.
+    /// The files are placed in a directory named after the proto file, or
+    /// after the package if its path is unknown.
+    fn generate_multi_file(&self, proto_file: &ProtoFile) -> BTreeMap<String, String> {
+        let registry = TypeRegistry::new(proto_file);
+        let graph = DependencyGraph::new(proto_file, &registry);
+        let directory = self.get_output_directory(proto_file);
+        
+        // Declarations grouped by top-level type, each group in dependency order
+        let mut groups: BTreeMap<&str, Vec<&Declaration>> = BTreeMap::new();
+        for declaration in graph.declarations() {
+            groups.entry(top_level_name(&declaration.full_name)).or_default().push(declaration);
+        }
+        
+        let mut generated_files = BTreeMap::new();
+        for (top_level, declarations) in &groups {
+            let mut content = String::new();
+            
+            // Add imports
+            writeln!(content, "{}", self.generate_imports()).unwrap();
+            for import in self.generate_custom_validator_imports(Some(top_level), &directory) {
+                writeln!(content, "{}", import).unwrap();
+            }
+            let message = proto_file.messages.iter().find(|message| message.name == *top_level);
+            let messages: Vec<&Message> = message.into_iter().collect();
+            for import in self.generate_type_imports(&messages, &[], &registry, &graph, &directory) {
+                writeln!(content, "{}", import).unwrap();
+            }
+            if self.accepts_proto_names() && has_renamed_fields(message.map_or(&[], std::slice::from_ref)) {
+                writeln!(content, "import {{ {} }} from \"./{}\";", PROTO_NAME_ALIASES, PROTO_NAMES_MODULE).unwrap();
+            }
+            
+            // Types declared in the other files of the package
+            let mut imports: BTreeMap<&str, BTreeSet<String>> = BTreeMap::new();
+            for declaration in declarations {
+                for reference in graph.references(&declaration.full_name) {
+                    let file = top_level_name(reference);
+                    if file != *top_level {
+                        let names = imports.entry(file).or_default();
+                        names.insert(self.schema_name(reference, self.parse_direction()));
+                        if self.domain_types {
+                            names.insert(self.schema_name(reference, Direction::Encode));
+                        }
+                        // Suffixed schemas do not bring the type along; it is
+                        // only used by written out types
+                        let typed = self.type_declarations == TypeDeclarations::Explicit
+                            || graph.is_recursive(&declaration.full_name);
+                        if !self.schema_suffix.is_empty() && typed && self.has_type(reference, &graph) {
+                            names.insert(format!("type {}", identifier(reference)));
+                        }
+                    }
+                }
+            }
+            for (file, names) in imports {
+                let names: Vec<String> = names.into_iter().collect();
+                writeln!(content, "import {{ {} }} from \"./{}\";", names.join(", "), identifier(file)).unwrap();
+            }
+            writeln!(content).unwrap();
+            
+            self.write_preamble(&mut content, message.map_or(&[], std::slice::from_ref), false);
+            
+            for declaration in declarations {
+                writeln!(content, "{}", self.generate_declaration(declaration, &registry, &graph)).unwrap();
+                writeln!(content).unwrap();
+            }
+            
+            generated_files.insert(format!("{}/{}.ts", directory, identifier(top_level)), content);
+        }
+        
+        // One file per service, importing the messages of its methods
+        let scope = TypeScope {
+            name: "",
+            registry: &registry,
+            graph: &graph,
+            imports: &self.imports,
+            direction: self.parse_direction(),
+        };
+        for service in &proto_file.services {
+            let mut content = String::new();
+            writeln!(content, "{}", self.generate_imports()).unwrap();
+            
+            if self.connect_client {
+                let mut runtime = vec!["ConnectTransportOptions"];
+                if service.methods.iter().any(|method| method_kind(method) == "unary") {
+                    runtime.push("connectUnary");
+                }
+                if service.methods.iter().any(|method| method_kind(method) == "server_streaming") {
+                    runtime.push("connectServerStream");
+                }
+                writeln!(content, "import {{ {} }} from \"./{}\";", runtime.join(", "), RUNTIME_MODULE).unwrap();
+            }
+            
+            let mut imports: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+            for method in &service.methods {
+                let types = [
+                    (&method.input_type, self.request_direction()),
+                    (&method.output_type, self.parse_direction()),
+                ];
+                for (type_name, direction) in types {
+                    if let Some(full_name) = registry.resolve("", type_name) {
+                        let file = identifier(top_level_name(&full_name));
+                        imports.entry(file).or_default().insert(self.schema_name(&full_name, direction));
+                    }
+                }
+            }
+            for (file, names) in imports {
+                let names: Vec<String> = names.into_iter().collect();
+                writeln!(content, "import {{ {} }} from \"./{}\";", names.join(", "), file).unwrap();
+            }
+            for import in self.generate_type_imports(&[], &[service], &registry, &graph, &directory) {
+                writeln!(content, "{}", import).unwrap();
+            }
+            writeln!(content).unwrap();
+            
+            writeln!(content, "{}", self.generate_service(service, &scope)).unwrap();
+            generated_files.insert(format!("{}/{}.ts", directory, service.name), content);
+        }
+        if self.connect_client && !proto_file.services.is_empty() {
+            let runtime = format!("{}\n\n{}\n", self.generate_imports(), connect_runtime(self.zod_version));
+            generated_files.insert(format!("{}/{}.ts", directory, RUNTIME_MODULE), runtime);
+        }
+        // The proto name helper is shared by the files of the package
+        if self.accepts_proto_names() && has_renamed_fields(&proto_file.messages) {
+            let helper = format!("export {}\n", proto_name_aliases_helper());
+            generated_files.insert(format!("{}/{}.ts", directory, PROTO_NAMES_MODULE), helper);
+        }
+        
+        // Barrel file
+        let mut index = String::new();
+        for top_level in groups.keys() {
+            writeln!(index, "export * from \"./{}\";", identifier(top_level)).unwrap();
+        }
+        for service in &proto_file.services {
+            writeln!(index, "export * from \"./{}\";", service.name).unwrap();
+        }
+        if self.connect_client && !proto_file.services.is_empty() {
+            writeln!(index, "export * from \"./{}\";", RUNTIME_MODULE).unwrap();
+        }
+        generated_files.insert(format!("{}/index.ts", directory), index);
+        
+        generated_files
+    }
+    
+    /// Write the file-level comment and the helpers used by the given messages
+    ///
+    /// The proto name helper is only written with `aliases`; multi-file output
+    /// imports it from its shared module instead.
+    fn write_preamble(&self, content: &mut String, messages: &[Message], aliases: bool) {
         // Add file-level comments if any
         if let Some(ref version) = self.metadata.file.version {
             writeln!(content, "// Generated from Protocol Buffer version {}", version).unwrap();
         }
         writeln!(content).unwrap();
         
-        // Generate enums
-        for enum_def in &proto_file.enums {
-            writeln!(content, "{}", self.generate_enum(enum_def)).unwrap();
+        // Shared schema for `google.protobuf.Struct` and friends
+        if self.well_known_types.uses_json_value(messages) {
+            writeln!(content, "{}", json_value_schema(self.zod_version)).unwrap();
             writeln!(content).unwrap();
         }
         
-        // Generate messages
-        for message in &proto_file.messages {
-            writeln!(content, "{}", self.generate_message(message)).unwrap();
+        // Renaming of proto field names to JSON names
+        if aliases && self.accepts_proto_names() && has_renamed_fields(messages) {
+            writeln!(content, "{}", proto_name_aliases_helper()).unwrap();
             writeln!(content).unwrap();
         }
         
-        content
+        // Conversion of `bytes` between base64 and `Uint8Array`
+        if self.domain_types && uses_bytes(messages) {
+            writeln!(content, "{}", bytes_helpers()).unwrap();
+            writeln!(content).unwrap();
+        }
+    }
+    
+    /// Generate the schema of an enum or message, followed by its encoder with domain types
+    fn generate_declaration(
+        &self,
+        declaration: &Declaration,
+        registry: &TypeRegistry,
+        graph: &DependencyGraph,
+    ) -> String {
+        let mut directions = vec![self.parse_direction()];
+        if self.domain_types {
+            directions.push(Direction::Encode);
+        }
+        
+        let schemas: Vec<String> = directions.into_iter()
+            .map(|direction| {
+                let scope = TypeScope {
+                    name: &declaration.full_name,
+                    registry,
+                    graph,
+                    imports: &self.imports,
+                    direction,
+                };
+                match declaration.kind {
+                    DeclarationKind::Enum(enum_def) => self.generate_enum(enum_def, &scope),
+                    DeclarationKind::Message(message) => self.generate_message(message, &declaration.scope, &scope),
+                }
+            })
+            .collect();
+        schemas.join("\n")
+    }
+    
+    /// Direction of the schemas parsing the wire form: into domain values with domain types
+    fn parse_direction(&self) -> Direction {
+        if self.domain_types { Direction::Decode } else { Direction::Wire }
+    }
+    
+    /// Direction of the schemas of requests sent by clients: from domain values with domain types
+    fn request_direction(&self) -> Direction {
+        if self.domain_types { Direction::Encode } else { Direction::Wire }
     }
     
     /// Generate the import statement based on the configured style
     fn generate_imports(&self) -> String {
+        let module = self.zod_version.module();
         match self.import_style {
-            ImportStyle::Default => "import z from 'zod';".to_string(),
-            ImportStyle::Named => "import { z } from 'zod';".to_string(),
-            ImportStyle::Namespace => "import * as z from 'zod';".to_string(),
+            ImportStyle::Default => format!("import z from \"{}\";", module),
+            ImportStyle::Named => format!("import {{ z }} from \"{}\";", module),
+            ImportStyle::Namespace => format!("import * as z from \"{}\";", module),
+        }
+    }
+    
+    /// Generate import statements for the custom validators referenced by annotations
+    ///
+    /// With a `top_level` type, only the validators used by that type and its
+    /// nested types are imported. Relative modules are resolved from `from_dir`,
+    /// the directory of the generated file within the output directory.
+    fn generate_custom_validator_imports(&self, top_level: Option<&str>, from_dir: &str) -> Vec<String> {
+        let mut modules: BTreeMap<&str, BTreeSet<&str>> = BTreeMap::new();
+        
+        let included = |name: &&String| {
+            top_level.is_none_or(|top_level| top_level_name(name) == top_level)
+        };
+        let all_metadata = std::iter::once(&self.metadata.file)
+            .chain(self.metadata.enums.iter()
+                .filter(|(name, _)| included(name))
+                .map(|(_, enum_metadata)| enum_metadata))
+            .chain(self.metadata.messages.iter()
+                .filter(|(name, _)| included(name))
+                .flat_map(|(_, message)| {
+                    std::iter::once(&message.message).chain(message.fields.values())
+                }));
+        
+        for metadata in all_metadata {
+            for (name, options) in metadata.custom.iter().flatten() {
+                if *options == Value::Bool(false) {
+                    continue;
+                }
+                if let Some(validator) = self.custom_validators.get(name) {
+                    modules.entry(&validator.import).or_default().insert(name);
+                }
+            }
+        }
+        
+        let depth = from_dir.split('/').filter(|part| !part.is_empty()).count();
+        modules.into_iter()
+            .map(|(module, names)| {
+                let names: Vec<&str> = names.into_iter().collect();
+                // Relative modules are resolved from the output directory
+                let module = match module.strip_prefix("./") {
+                    Some(path) if depth > 0 => format!("{}{}", "../".repeat(depth), path),
+                    _ if depth > 0 && module.starts_with("../") => format!("{}{}", "../".repeat(depth), module),
+                    _ => module.to_string(),
+                };
+                format!("import {{ {} }} from \"{}\";", names.join(", "), module)
+            })
+            .collect()
+    }
+    
+    /// Generate import statements for the types of imported files used by the
+    /// messages and the methods of the services
+    fn generate_type_imports(
+        &self,
+        messages: &[&Message],
+        services: &[&Service],
+        registry: &TypeRegistry,
+        graph: &DependencyGraph,
+        from_dir: &str,
+    ) -> Vec<String> {
+        let mut modules: BTreeMap<&str, BTreeSet<String>> = BTreeMap::new();
+        for message in messages {
+            self.collect_imported_types(message, &message.name, registry, graph, &mut modules);
+        }
+        for method in services.iter().flat_map(|service| &service.methods) {
+            self.collect_imported_type(&method.input_type, "", false, registry, &mut modules);
+            self.collect_imported_type(&method.output_type, "", false, registry, &mut modules);
+        }
+        
+        modules.into_iter()
+            .map(|(module, bindings)| {
+                let bindings: Vec<String> = bindings.into_iter().collect();
+                format!("import {{ {} }} from \"{}\";", bindings.join(", "), self.module_specifier(module, from_dir))
+            })
+            .collect()
+    }
+    
+    /// Collect the import bindings of the imported types referenced by a message
+    /// and its nested messages, keyed by module
+    fn collect_imported_types<'a>(
+        &'a self,
+        message: &Message,
+        full_name: &str,
+        registry: &TypeRegistry,
+        graph: &DependencyGraph,
+        modules: &mut BTreeMap<&'a str, BTreeSet<String>>,
+    ) {
+        // Only written out types refer to the types of other messages
+        let typed = self.generate_types
+            && (self.type_declarations == TypeDeclarations::Explicit || graph.is_recursive(full_name));
+        let oneof_fields = message.oneofs.iter().flat_map(|oneof| &oneof.fields);
+        for field in message.fields.iter().chain(oneof_fields) {
+            let types = match field.typ {
+                FieldType::Map(ref key_type, ref value_type) => vec![key_type.as_ref(), value_type.as_ref()],
+                ref typ => vec![typ],
+            };
+            for typ in types {
+                if let FieldType::MessageOrEnum(ref type_name) = *typ {
+                    self.collect_imported_type(type_name, full_name, typed, registry, modules);
+                }
+            }
+        }
+        
+        for nested in &message.nested_messages {
+            self.collect_imported_types(nested, &qualified_name(full_name, &nested.name), registry, graph, modules);
+        }
+    }
+    
+    /// Collect the import binding of a type reference made inside `scope`, if
+    /// it is declared in an imported file
+    ///
+    /// The type itself is only imported for references from a `typed`
+    /// declaration, whose type is written out.
+    fn collect_imported_type<'a>(
+        &'a self,
+        type_name: &str,
+        scope: &str,
+        typed: bool,
+        registry: &TypeRegistry,
+        modules: &mut BTreeMap<&'a str, BTreeSet<String>>,
+    ) {
+        if registry.resolve(scope, type_name).is_some() {
+            return;
+        }
+        if let Some(imported) = self.imports.resolve(registry.package(), scope, type_name) {
+            let (export, local) = self.import_binding(imported, registry);
+            let binding = |prefix: &str, suffix: &str| {
+                if export == local {
+                    format!("{}{}{}", prefix, export, suffix)
+                } else {
+                    format!("{}{}{} as {}{}", prefix, export, suffix, local, suffix)
+                }
+            };
+            let bindings = modules.entry(&imported.module).or_default();
+            bindings.insert(binding("", &self.schema_suffix));
+            if self.domain_types {
+                bindings.insert(binding("", &self.name_suffix(Direction::Encode)));
+            }
+            // Suffixed schemas do not bring the type along
+            if !self.schema_suffix.is_empty() && typed {
+                bindings.insert(binding("type ", ""));
+            }
+        }
+    }
+    
+    /// Name exported by the module of an imported type, and its local name
+    ///
+    /// With namespaced naming only the top-level type is imported. Names clashing
+    /// with a type of this file or of another import are prefixed with the package.
+    fn import_binding(&self, imported: &ImportedType, registry: &TypeRegistry) -> (String, String) {
+        let export = match self.nested_naming {
+            NestedTypeNaming::Namespace => identifier(top_level_name(&imported.name)),
+            NestedTypeNaming::Underscore => identifier(&imported.name),
+        };
+        let clashes = registry.types().any(|(name, _)| identifier(name) == export)
+            || self.imports.is_ambiguous(imported);
+        let local = match imported.package {
+            Some(ref package) if clashes => format!("{}_{}", identifier(package), export),
+            _ => export.clone(),
+        };
+        (export, local)
+    }
+    
+    /// Expression referring to an imported type, or to its schema with the schema suffix
+    fn imported_reference(&self, imported: &ImportedType, registry: &TypeRegistry, suffix: &str) -> String {
+        let (_, local) = self.import_binding(imported, registry);
+        match (self.nested_naming, imported.name.split_once('.')) {
+            (NestedTypeNaming::Namespace, Some((_, nested))) => format!("{}{}.{}", local, suffix, nested),
+            _ => format!("{}{}", local, suffix),
+        }
+    }
+    
+    /// Name of the schema of a type for `direction`
+    fn schema_name(&self, full_name: &str, direction: Direction) -> String {
+        format!("{}{}", identifier(full_name), self.name_suffix(direction))
+    }
+    
+    /// Suffix of the names of the schemas for `direction`
+    fn name_suffix(&self, direction: Direction) -> String {
+        match direction {
+            Direction::Encode => format!("{}{}", ENCODER_SUFFIX, self.schema_suffix),
+            Direction::Wire | Direction::Decode => self.schema_suffix.clone(),
+        }
+    }
+    
+    /// Whether a TypeScript type is declared for a type of the file
+    ///
+    /// Recursive messages always have one, as their schemas are annotated with it.
+    fn has_type(&self, full_name: &str, graph: &DependencyGraph) -> bool {
+        self.generate_types || graph.is_recursive(full_name)
+    }
+    
+    /// Module specifier of the generated module of an imported proto file
+    ///
+    /// The longest matching alias replaces the start of the proto path;
+    /// otherwise the module is imported relative to `from_dir`.
+    fn module_specifier(&self, module: &str, from_dir: &str) -> String {
+        let alias = self.import_aliases.iter()
+            .filter(|(prefix, _)| module.starts_with(module_path(prefix).as_str()))
+            .max_by_key(|(prefix, _)| prefix.len());
+        match alias {
+            Some((prefix, specifier)) => format!("{}{}", specifier, &module[module_path(prefix).len()..]),
+            None => relative_module(from_dir, module),
+        }
+    }
+    
+    /// Generate the method descriptors of a service and, if enabled, its client
+    ///
+    /// Client- and bidi-streaming methods are left out of the client, as `fetch`
+    /// cannot stream requests.
+    fn generate_service(&self, service: &Service, scope: &TypeScope) -> String {
+        let mut content = String::new();
+        let type_name = qualified_name(scope.registry.package().unwrap_or(""), &service.name);
+        
+        writeln!(content, "export const {} = {{", service.name).unwrap();
+        writeln!(content, "  typeName: \"{}\",", type_name).unwrap();
+        writeln!(content, "  methods: {{").unwrap();
+        let request_scope = TypeScope { direction: self.request_direction(), ..*scope };
+        let response_scope = TypeScope { direction: self.parse_direction(), ..*scope };
+        for method in &service.methods {
+            let input = self.type_to_zod_type(&FieldType::MessageOrEnum(method.input_type.clone()), &request_scope);
+            let output = self.type_to_zod_type(&FieldType::MessageOrEnum(method.output_type.clone()), &response_scope);
+            writeln!(content, "    {}: {{", method_key(&method.name)).unwrap();
+            writeln!(content, "      name: \"{}\",", method.name).unwrap();
+            writeln!(content, "      input: {},", input).unwrap();
+            writeln!(content, "      output: {},", output).unwrap();
+            writeln!(content, "      kind: \"{}\",", method_kind(method)).unwrap();
+            writeln!(content, "    }},").unwrap();
         }
+        writeln!(content, "  }},").unwrap();
+        write!(content, "}} as const;").unwrap();
+        
+        if !self.connect_client {
+            return content;
+        }
+        
+        writeln!(content).unwrap();
+        writeln!(content).unwrap();
+        writeln!(content, "export function create{}Client(options: ConnectTransportOptions) {{", service.name).unwrap();
+        writeln!(content, "  return {{").unwrap();
+        for method in &service.methods {
+            let call = match method_kind(method) {
+                "unary" => "connectUnary",
+                "server_streaming" => "connectServerStream",
+                _ => {
+                    warn!("Streaming requests are not supported by the Connect client, skipping {}.{}",
+                        service.name, method.name);
+                    continue;
+                }
+            };
+            let descriptor = format!("{}.methods.{}", service.name, method_key(&method.name));
+            writeln!(content, "    {}: (request: z.input<typeof {}.input>, signal?: AbortSignal) =>",
+                method_key(&method.name), descriptor).unwrap();
+            writeln!(content, "      {}(options, {}, {}, request, signal),", call, service.name, descriptor).unwrap();
+        }
+        writeln!(content, "  }};").unwrap();
+        write!(content, "}}").unwrap();
+        
+        content
     }
     
     /// Generate a Zod enum definition
-    fn generate_enum(&self, enum_def: &Enum) -> String {
+    ///
+    /// `scope` is the dotted name of the enclosing message, empty for top-level enums.
+    /// Encoders only accept the domain values, which are valid on the wire as they are.
+    fn generate_enum(&self, enum_def: &Enum, scope: &TypeScope) -> String {
         let mut content = String::new();
+        let full_name = scope.name.to_string();
+        let name = identifier(&full_name);
+        let direction = scope.direction;
+        let encode = direction == Direction::Encode;
         
-        // Get metadata for this enum if available
-        let enum_metadata = self.metadata.enums.get(&enum_def.name).cloned()
+        // Get metadata for this enum if available; its default is a value of the enum
+        let mut enum_metadata = self.metadata.enums.get(&full_name).cloned()
             .unwrap_or_default();
+        let default = enum_metadata.default.take();
+        
+        // Deprecated values may be left out
+        let enum_values: Vec<&EnumValue> = enum_def.values.iter()
+            .filter(|value| !(self.omit_deprecated && is_deprecated_value(value)))
+            .collect();
+        
+        // Comments, description and deprecation of the declarations; values of
+        // `z.enum` cannot carry their own, so they are listed in the enum's
+        let members = match self.enum_style {
+            EnumStyle::Names | EnumStyle::Union => self.member_doc_lines(&full_name, &enum_values),
+            EnumStyle::TsEnum | EnumStyle::ConstObject => Vec::new(),
+        };
+        let mut docs = self.declaration_docs(&full_name, &enum_metadata, &members, &enum_def.options, encode);
+        let enum_metadata = self.describe_with_comment(enum_metadata, &full_name);
+        
+        let names: Vec<String> = enum_values.iter()
+            .map(|value| format!("\"{}\"", value.name))
+            .collect();
+        
+        // Aliases share a number, which is only accepted once
+        let mut numbers: Vec<(i64, &str)> = Vec::new();
+        for value in &enum_values {
+            let number = enum_number(&value.number);
+            if !numbers.iter().any(|(other, _)| *other == number) {
+                numbers.push((number, &value.name));
+            } else if !allows_alias(enum_def) && !encode {
+                warn!("Enum {} reuses number {} without allow_alias", full_name, number);
+            }
+        }
+        // Domain values are names, so decoded numbers are converted to the name of their value
+        let literals = numbers.iter().map(|(number, value_name)| match direction {
+            Direction::Decode => self.zod_version.transform(&format!("z.literal({})", number),
+                &format!("() => \"{}\" as const", value_name)),
+            _ => format!("z.literal({})", number),
+        });
+        
+        // Schemas accepted by the enum, combined in a union if there are several,
+        // and the TypeScript types of the values they output
+        let mut alternatives = Vec::new();
+        let mut ts_types = Vec::new();
+        match self.enum_style {
+            EnumStyle::Names | EnumStyle::Union => {
+                alternatives.push(format!("z.enum([{}])", names.join(", ")));
+                ts_types.extend(names.iter().cloned());
+                // The JSON mapping also accepts enum numbers
+                if !encode && (self.enum_style == EnumStyle::Union || self.json_mapping.enabled) {
+                    alternatives.extend(literals);
+                    if direction == Direction::Wire {
+                        ts_types.extend(numbers.iter().map(|(number, _)| number.to_string()));
+                    }
+                }
+            }
+            EnumStyle::TsEnum | EnumStyle::ConstObject => {
+                let values: Vec<String> = enum_values.iter()
+                    .map(|value| {
+                        let path = qualified_name(&full_name, &value.name);
+                        let doc = jsdoc(&self.doc_lines(&path, None), is_deprecated_value(value), "  ");
+                        let number = enum_number(&value.number);
+                        match self.enum_style {
+                            EnumStyle::TsEnum => format!("{}  {} = {},", doc, value.name, number),
+                            _ => format!("{}  {}: {},", doc, value.name, number),
+                        }
+                    })
+                    .collect();
+                let (values_name, values_type) = if self.enum_style == EnumStyle::TsEnum {
+                    let values_name = format!("{}Enum", name);
+                    if !encode {
+                        content.push_str(&docs.take());
+                        writeln!(content, "export enum {} {{\n{}\n}}", values_name, values.join("\n")).unwrap();
+                    }
+                    (values_name.clone(), values_name)
+                } else {
+                    let values_name = format!("{}Values", name);
+                    if !encode {
+                        content.push_str(&docs.take());
+                        writeln!(content, "export const {} = {{\n{}\n}} as const;", values_name,
+                            values.join("\n")).unwrap();
+                    }
+                    let values_type = format!("(typeof {0})[keyof typeof {0}]", values_name);
+                    (values_name, values_type)
+                };
+                let names_schema = format!("z.enum([{}])", names.join(", "));
+                match direction {
+                    Direction::Wire => {
+                        alternatives.push(self.zod_version.native_enum(&values_name));
+                        ts_types.push(values_type);
+                        // The JSON mapping also accepts enum names
+                        if self.json_mapping.enabled {
+                            alternatives.push(names_schema);
+                            ts_types.extend(names.iter().cloned());
+                        }
+                    }
+                    // Domain values are names, as with the other styles
+                    Direction::Decode => {
+                        alternatives.extend(literals);
+                        if self.json_mapping.enabled {
+                            alternatives.push(names_schema);
+                        }
+                        ts_types.extend(names.iter().cloned());
+                    }
+                    // Names are encoded to their number through the values
+                    Direction::Encode => alternatives.push(self.zod_version.transform(&names_schema,
+                        &format!("(value) => {}[value]", values_name))),
+                }
+            }
+        }
         
-        // Add description comment if available
-        if let Some(ref description) = enum_metadata.description {
-            writeln!(content, "/**\n * {}\n */", description).unwrap();
+        // Open enums accept numbers added in later versions of the schema
+        if self.open_enums {
+            alternatives.push(self.zod_version.integer().to_string());
+            ts_types.push("number".to_string());
         }
         
         // Start enum definition
-        write!(content, "export const {} = z.enum([", enum_def.name).unwrap();
+        let schema = if alternatives.len() == 1 {
+            alternatives.remove(0)
+        } else {
+            format!("z.union([{}])", alternatives.join(", "))
+        };
         
-        // Add enum values
-        let enum_values: Vec<String> = enum_def.values.iter()
-            .map(|value| format!("'{}'", value.name))
-            .collect();
-            
-        write!(content, "{}])", enum_values.join(", ")).unwrap();
+        // Encoders only validate the values, their types are those of the schema
+        let schema_name = self.schema_name(&full_name, direction);
+        if encode {
+            content.push_str(&docs.take());
+            writeln!(content, "export const {} = {};", schema_name, schema).unwrap();
+            return content;
+        }
+        
+        // Written out types come before the schema
+        let explicit = self.generate_types && self.type_declarations == TypeDeclarations::Explicit;
+        if explicit {
+            let ts_type = self.branded_ts_type(ts_types.join(" | "), &enum_metadata);
+            content.push_str(&docs.take());
+            writeln!(content, "export type {} = {};", name, ts_type).unwrap();
+        }
         
         // Add metadata constraints if available
-        self.apply_metadata_constraints(&mut content, &enum_metadata);
+        let mut schema = self.apply_metadata_constraints(schema, &enum_metadata, false);
+        if let Some(ref default) = default {
+            let enum_type = FieldType::MessageOrEnum(self.absolute_name(&full_name, scope));
+            match self.literal(&enum_type, default, scope) {
+                Some(literal) => {
+                    schema = self.apply_default(&schema, &literal, self.transforms_input(&enum_type, direction));
+                }
+                None => warn!("Default value {} is not a value of enum {}, ignoring it", default, full_name),
+            }
+        }
+        content.push_str(&docs.take());
+        write!(content, "export const {} = {}", schema_name, schema).unwrap();
         
         // Close the statement
         writeln!(content, ";").unwrap();
         
         // Add type alias
-        writeln!(content, "export type {} = z.infer<typeof {}>;", 
-            enum_def.name, enum_def.name).unwrap();
+        if self.generate_types {
+            self.write_type_aliases(&mut content, &mut docs, &name, &schema_name, !explicit);
+        }
         
         content
     }
     
+    /// Write the inferred type of a schema and, with domain types, the type of its wire form
+    fn write_type_aliases(
+        &self,
+        content: &mut String,
+        docs: &mut DeclarationDocs,
+        name: &str,
+        schema_name: &str,
+        inferred: bool,
+    ) {
+        if inferred {
+            let output = if self.domain_types { "z.output" } else { "z.infer" };
+            content.push_str(&docs.take());
+            writeln!(content, "export type {} = {}<typeof {}>;", name, output, schema_name).unwrap();
+        }
+        if self.domain_types {
+            content.push_str(&docs.take());
+            writeln!(content, "export type {}Wire = z.input<typeof {}>;", name, schema_name).unwrap();
+        }
+    }
+    
     /// Generate a Zod message definition
-    fn generate_message(&self, message: &Message) -> String {
+    ///
+    /// `parent` is the dotted name of the enclosing message, empty for
+    /// top-level messages. Nested types are generated separately and, through
+    /// the dependency order, before the message. Encoders have no types of their
+    /// own and are not attached to their parent.
+    fn generate_message(&self, message: &Message, parent: &str, scope: &TypeScope) -> String {
         let mut content = String::new();
+        let full_name = scope.name;
+        let name = identifier(full_name);
+        let encode = scope.direction == Direction::Encode;
+        let namespaced = self.nested_naming == NestedTypeNaming::Namespace && !encode
+            && (!message.nested_messages.is_empty() || !message.nested_enums.is_empty());
+        let recursive = scope.graph.is_recursive(full_name);
         
         // Get metadata for this message if available
-        let message_metadata = self.metadata.messages.get(&message.name).cloned()
+        let mut message_metadata = self.metadata.messages.get(full_name).cloned()
             .unwrap_or_default();
         
-        // Add description comment if available
-        if let Some(ref description) = message_metadata.message.description {
-            writeln!(content, "/**\n * {}\n */", description).unwrap();
+        // Comments, description and deprecation of the declarations
+        let mut docs = self.declaration_docs(full_name, &message_metadata.message, &[], &message.options, encode);
+        message_metadata.message = self.describe_with_comment(message_metadata.message, full_name);
+        
+        // Deprecated fields may be left out
+        let retained;
+        let message = if self.omit_deprecated {
+            let retain = |fields: &[Field]| -> Vec<Field> {
+                fields.iter().filter(|field| !is_deprecated(&field.options)).cloned().collect()
+            };
+            retained = Message {
+                fields: retain(&message.fields),
+                oneofs: message.oneofs.iter()
+                    .map(|oneof| OneOf { fields: retain(&oneof.fields), ..oneof.clone() })
+                    .filter(|oneof| !oneof.fields.is_empty())
+                    .collect(),
+                ..message.clone()
+            };
+            &retained
+        } else {
+            message
+        };
+        
+        // Recursive types cannot be inferred, so their type is written out
+        // and the schema annotated with it
+        let explicit = self.generate_types && self.type_declarations == TypeDeclarations::Explicit;
+        if !encode && (recursive || explicit) {
+            let ts_type = self.message_ts_type(message, &message_metadata.fields, scope);
+            // Intersections with oneof unions cannot be interfaces
+            let intersected = self.oneof_style == OneofStyle::Union && !message.oneofs.is_empty();
+            content.push_str(&docs.take());
+            if explicit && !intersected {
+                writeln!(content, "export interface {} {}", name, ts_type).unwrap();
+            } else {
+                writeln!(content, "export type {} = {};", name, ts_type).unwrap();
+            }
         }
         
         // Start message definition
-        writeln!(content, "export const {} = z.object({{", message.name).unwrap();
+        let schema_name = self.schema_name(full_name, scope.direction);
+        content.push_str(&docs.take());
+        write!(content, "export const {}", schema_name).unwrap();
+        // Encoders output JSON names, so only the parsing schemas accept proto names
+        let aliases = if self.accepts_proto_names() && !encode { renamed_fields(message) } else { Vec::new() };
+        if recursive {
+            // Inputs may differ from the output: int64 unions, defaults and
+            // converted values are only checked against the type they parse to
+            let annotation = match scope.direction {
+                Direction::Wire | Direction::Decode => self.zod_version.type_annotation(&name, Some("unknown")),
+                Direction::Encode => self.zod_version.type_annotation("unknown", Some(&name)),
+            };
+            write!(content, ": {}", annotation).unwrap();
+            if namespaced {
+                let nested: Vec<String> = Self::nested_names(message)
+                    .map(|nested_name| format!("{}: typeof {}", nested_name,
+                        self.schema_name(&qualified_name(full_name, nested_name), scope.direction)))
+                    .collect();
+                write!(content, " & {{ {} }}", nested.join("; ")).unwrap();
+            }
+        }
+        write!(content, " = ").unwrap();
+        if namespaced {
+            write!(content, "Object.assign(").unwrap();
+        }
         
         // Add fields
+        let mut shape = String::new();
+        writeln!(shape, "{{").unwrap();
         for field in &message.fields {
-            writeln!(content, "{},", self.generate_field(field, &message_metadata.fields))
+            writeln!(shape, "{},", self.generate_field(field, &message_metadata.fields, scope))
                 .unwrap();
         }
         
-        // Close object definition
-        write!(content, "}})").unwrap();
+        // Add oneofs that are represented inside the object
+        let unknown_keys = message_metadata.message.unknown_keys.as_ref().unwrap_or(&self.unknown_keys);
+        let mut rules = message_metadata.message.refinements.clone().unwrap_or_default();
+        for oneof in &message.oneofs {
+            match self.oneof_style {
+                OneofStyle::OptionalKeys => {
+                    self.generate_oneof_keys(&mut shape, oneof, &message_metadata.fields, scope);
+                    rules.push(CrossFieldRule::AtMostOne {
+                        fields: oneof.fields.iter().map(|field| field.name.clone()).collect(),
+                        message: None,
+                    });
+                }
+                OneofStyle::DiscriminatedUnion => {
+                    self.generate_oneof_discriminated_union(&mut shape, oneof,
+                        &message_metadata.fields, scope);
+                }
+                // The variants check the members; the object only has to let them through
+                OneofStyle::Union if *unknown_keys != UnknownKeys::Strip => {
+                    for field in &oneof.fields {
+                        writeln!(shape, "  {}: {},", self.field_key(field),
+                            self.zod_version.wrap("z.unknown()", "optional")).unwrap();
+                    }
+                }
+                OneofStyle::Union => {}
+            }
+        }
+        
+        // Close object definition; unknown keys are treated before the object is combined
+        write!(shape, "}}").unwrap();
+        let mut schema = self.zod_version.object(&shape, unknown_keys);
+        
+        // Add oneofs that are represented as unions of variants
+        if self.oneof_style == OneofStyle::Union {
+            for oneof in &message.oneofs {
+                let variants = self.oneof_union(oneof, &message_metadata.fields, scope);
+                schema = self.zod_version.intersection(&schema, &variants);
+            }
+        }
+        
+        // Apply cross-field rules
+        if !rules.is_empty() {
+            schema = self.apply_refinements(schema, message, &rules, scope.direction);
+        }
+        
+        // Objects keyed by proto names are renamed before validation
+        if !aliases.is_empty() {
+            let names: Vec<String> = aliases.iter()
+                .map(|(proto, json)| format!("{}: {}", proto, Value::from(json.as_str())))
+                .collect();
+            let rename = format!("{}({{ {} }})", PROTO_NAME_ALIASES, names.join(", "));
+            schema = format!("{}{})", self.zod_version.preprocess_start(&rename), schema);
+        }
         
         // Apply message-level metadata constraints if available
-        self.apply_metadata_constraints(&mut content, &message_metadata.message);
+        let default = message_metadata.message.default.take();
+        let mut schema = self.apply_metadata_constraints(schema, &message_metadata.message, false);
+        if let Some(ref default) = default {
+            match self.message_literal(message, default, scope) {
+                // Parsed, so that the defaults of the fields it leaves out are filled in
+                Some(literal) => schema = self.apply_default(&schema, &literal, true),
+                None => warn!("Default value {} does not fit message {}, ignoring it", default, full_name),
+            }
+        }
+        write!(content, "{}", schema).unwrap();
+        
+        // Attach nested schemas to the message schema, e.g. `ComplexMessage.Address`
+        if namespaced {
+            writeln!(content, ", {{").unwrap();
+            for nested_name in Self::nested_names(message) {
+                writeln!(content, "  {}: {},", nested_name,
+                    self.schema_name(&qualified_name(full_name, nested_name), scope.direction)).unwrap();
+            }
+            write!(content, "}})").unwrap();
+        }
         
         // Close the statement
         writeln!(content, ";").unwrap();
         
         // Add type alias
-        writeln!(content, "export type {} = z.infer<typeof {}>;", 
-            message.name, message.name).unwrap();
+        if self.generate_types && !encode {
+            self.write_type_aliases(&mut content, &mut docs, &name, &schema_name, !recursive && !explicit);
+        }
+        
+        // Expose the nested types once, from the top-level message
+        if namespaced && parent.is_empty() && self.generate_types {
+            self.write_type_namespace(&mut content, message, full_name, 0);
+        }
+        
+        content
+    }
+    
+    /// Names of the enums and messages nested in a message
+    fn nested_names(message: &Message) -> impl Iterator<Item = &String> {
+        message.nested_enums.iter().map(|e| &e.name)
+            .chain(message.nested_messages.iter().map(|m| &m.name))
+    }
+    
+    /// Write out the TypeScript type of a message
+    ///
+    /// Used for recursive messages, whose type cannot be inferred from the schema.
+    fn message_ts_type(
+        &self,
+        message: &Message,
+        field_metadatas: &HashMap<String, ZodMetadata>,
+        scope: &TypeScope,
+    ) -> String {
+        let mut content = String::new();
+        writeln!(content, "{{").unwrap();
+        
+        for field in &message.fields {
+            let field_metadata = field_metadatas.get(&field.name).cloned().unwrap_or_default();
+            let presence = self.field_presence(field, &field_metadata, scope);
+            let optional = if matches!(presence, Presence::Optional) { "?" } else { "" };
+            write!(content, "{}", self.property_doc(field, &field_metadata, scope)).unwrap();
+            writeln!(content, "  {}{}: {};", self.field_key(field), optional,
+                self.field_ts_type(field, &field_metadata, scope)).unwrap();
+        }
+        
+        let oneof_ts_type = |field: &Field| {
+            let field_metadata = field_metadatas.get(&field.name).cloned().unwrap_or_default();
+            self.field_ts_type(field, &field_metadata, scope)
+        };
+        let mut unions = Vec::new();
+        for oneof in &message.oneofs {
+            match self.oneof_style {
+                OneofStyle::OptionalKeys => {
+                    for field in &oneof.fields {
+                        let field_metadata = field_metadatas.get(&field.name).cloned().unwrap_or_default();
+                        write!(content, "{}", self.property_doc(field, &field_metadata, scope)).unwrap();
+                        writeln!(content, "  {}?: {};", self.field_key(field), oneof_ts_type(field)).unwrap();
+                    }
+                }
+                OneofStyle::DiscriminatedUnion => {
+                    let cases: Vec<String> = oneof.fields.iter()
+                        .map(|field| format!("{{ case: \"{}\"; value: {} }}", self.field_key(field),
+                            oneof_ts_type(field)))
+                        .chain(std::iter::once("{ case: undefined; value?: undefined }".to_string()))
+                        .collect();
+                    writeln!(content, "  {}: {};", self.oneof_key(oneof), cases.join(" | ")).unwrap();
+                }
+                OneofStyle::Union => {
+                    let variants: Vec<String> = oneof.fields.iter().map(Some).chain(std::iter::once(None))
+                        .map(|set_field| {
+                            let members: Vec<String> = oneof.fields.iter()
+                                .map(|field| match set_field {
+                                    Some(set_field) if set_field.name == field.name => {
+                                        format!("{}: {}", self.field_key(field), oneof_ts_type(field))
+                                    }
+                                    _ => format!("{}?: undefined", self.field_key(field)),
+                                })
+                                .collect();
+                            format!("{{ {} }}", members.join("; "))
+                        })
+                        .collect();
+                    unions.push(format!("({})", variants.join(" | ")));
+                }
+            }
+        }
+        
+        write!(content, "}}").unwrap();
+        for union in unions {
+            write!(content, " & {}", union).unwrap();
+        }
         
         content
     }
     
+    /// TypeScript type of a field value
+    fn field_ts_type(&self, field: &Field, field_metadata: &ZodMetadata, scope: &TypeScope) -> String {
+        let typ = self.branded_ts_type(self.ts_type(&field.typ, scope), field_metadata);
+        let typ = match field.label {
+            FieldLabel::Repeated if field_metadata.brand.is_some() => format!("({})[]", typ),
+            FieldLabel::Repeated => format!("{}[]", typ),
+            _ => typ,
+        };
+        if field_metadata.nullable == Some(true) {
+            format!("{} | null", typ)
+        } else {
+            typ
+        }
+    }
+    
+    /// TypeScript type of a value with the brand of its annotation, if any
+    fn branded_ts_type(&self, ts_type: String, metadata: &ZodMetadata) -> String {
+        let brand = match metadata.brand {
+            Some(ref brand) => Value::from(brand.as_str()).to_string(),
+            None => return ts_type,
+        };
+        if ts_type.contains(" | ") {
+            self.zod_version.branded_type(&format!("({})", ts_type), &brand)
+        } else {
+            self.zod_version.branded_type(&ts_type, &brand)
+        }
+    }
+    
+    /// TypeScript type corresponding to a FieldType
+    ///
+    /// With domain types, this is the type of the domain values.
+    fn ts_type(&self, field_type: &FieldType, scope: &TypeScope) -> String {
+        match field_type {
+            FieldType::Int64 | FieldType::UInt64 |
+            FieldType::SInt64 | FieldType::Fixed64 |
+            FieldType::SFixed64 => match self.int64_strategy {
+                _ if self.domain_types => "bigint".to_string(),
+                Int64Strategy::String => "string".to_string(),
+                Int64Strategy::Bigint | Int64Strategy::Union => "bigint".to_string(),
+            },
+            FieldType::Double | FieldType::Float |
+            FieldType::Int32 | FieldType::UInt32 |
+            FieldType::SInt32 | FieldType::Fixed32 |
+            FieldType::SFixed32 => "number".to_string(),
+            FieldType::Bool => "boolean".to_string(),
+            FieldType::Bytes if self.domain_types => "Uint8Array".to_string(),
+            FieldType::String | FieldType::Bytes => "string".to_string(),
+            // Types that are not declared are inferred from their schema
+            FieldType::MessageOrEnum(ref type_name) => match scope.resolve(type_name) {
+                Some(full_name) if self.has_type(&full_name, scope.graph) => identifier(&full_name),
+                Some(full_name) => {
+                    format!("z.infer<typeof {}>", self.schema_name(&full_name, self.parse_direction()))
+                }
+                None => match scope.resolve_import(type_name) {
+                    Some(imported) if self.generate_types => self.imported_reference(imported, scope.registry, ""),
+                    Some(imported) => format!("z.infer<typeof {}>",
+                        self.imported_reference(imported, scope.registry, &self.schema_suffix)),
+                    None => match self.well_known_types.schema(type_name, self.zod_version, scope.direction) {
+                        Some(WellKnownSchema::Wrapper(ref scalar)) => {
+                            format!("{} | null", self.ts_type(scalar, scope))
+                        }
+                        _ => self.well_known_types.ts_type(type_name, self.domain_types)
+                            .unwrap_or_else(|| "unknown".to_string()),
+                    },
+                },
+            },
+            FieldType::Map(ref key_type, ref value_type) => {
+                let value = self.ts_type(value_type, scope);
+                match **key_type {
+                    FieldType::Bool => format!("Partial<Record<\"true\" | \"false\", {}>>", value),
+                    FieldType::MessageOrEnum(_) => {
+                        format!("Partial<Record<{}, {}>>", self.ts_type(key_type, scope), value)
+                    }
+                    _ => format!("Record<string, {}>", value),
+                }
+            }
+        }
+    }
+    
+    /// Write a namespace exposing the types of nested messages and enums as `Outer.Inner`
+    ///
+    /// The namespace only declares types, so it merges with the schema constant
+    /// of the same name.
+    fn write_type_namespace(&self, content: &mut String, message: &Message, full_name: &str, depth: usize) {
+        let indent = "  ".repeat(depth);
+        writeln!(content, "{}export namespace {} {{", indent, message.name).unwrap();
+        
+        for enum_def in &message.nested_enums {
+            writeln!(content, "{}  export type {} = {};", indent, enum_def.name,
+                identifier(&qualified_name(full_name, &enum_def.name))).unwrap();
+        }
+        for nested_message in &message.nested_messages {
+            let nested_full_name = qualified_name(full_name, &nested_message.name);
+            writeln!(content, "{}  export type {} = {};", indent, nested_message.name,
+                identifier(&nested_full_name)).unwrap();
+            if !nested_message.nested_messages.is_empty() || !nested_message.nested_enums.is_empty() {
+                self.write_type_namespace(content, nested_message, &nested_full_name, depth + 1);
+            }
+        }
+        
+        writeln!(content, "{}}}", indent).unwrap();
+    }
+    
+    /// Apply cross-field rules as a single `.superRefine` on the message object
+    ///
+    /// Each failing rule adds an issue at the path of the field it concerns, so
+    /// that form libraries can show the error next to the right input.
+    fn apply_refinements(
+        &self,
+        schema: String,
+        message: &Message,
+        rules: &[CrossFieldRule],
+        direction: Direction,
+    ) -> String {
+        let mut checks = String::new();
+        
+        for rule in rules {
+            // Skip rules that reference fields the message does not have; oneof
+            // members are only top-level keys when not generated as `{ case, value }`
+            let oneof_fields = message.oneofs.iter()
+                .filter(|_| self.oneof_style != OneofStyle::DiscriminatedUnion)
+                .flat_map(|oneof| &oneof.fields);
+            let fields: Vec<&Field> = message.fields.iter().chain(oneof_fields).collect();
+            let missing = rule.fields().into_iter()
+                .find(|name| !fields.iter().any(|field| field.name == *name));
+            if let Some(name) = missing {
+                warn!("Refinement on {} references unknown field '{}', skipping it", message.name, name);
+                continue;
+            }
+            
+            // Rules name proto fields; the checks use the object keys
+            let field = |name: &String| *fields.iter().find(|field| field.name == *name).unwrap();
+            let key = |name: &String| self.field_key(field(name));
+            let ordered = |name: &String| self.ordered_value(field(name), &access(&key(name)), direction);
+            
+            let (condition, paths, default_message) = match rule {
+                CrossFieldRule::After { field, other, .. } => {
+                    let (value, other_value) = (ordered(field), ordered(other));
+                    let (field, other) = (key(field), key(other));
+                    (
+                        format!("{} != null && {} != null && !({} > {})",
+                            access(&field), access(&other), value, other_value),
+                        vec![field.clone()],
+                        format!("{} must be after {}", field, other),
+                    )
+                }
+                CrossFieldRule::Before { field, other, .. } => {
+                    let (value, other_value) = (ordered(field), ordered(other));
+                    let (field, other) = (key(field), key(other));
+                    (
+                        format!("{} != null && {} != null && !({} < {})",
+                            access(&field), access(&other), value, other_value),
+                        vec![field.clone()],
+                        format!("{} must be before {}", field, other),
+                    )
+                }
+                CrossFieldRule::Equals { field, other, .. } => {
+                    let (field, other) = (key(field), key(other));
+                    (
+                        format!("{} !== {}", access(&field), access(&other)),
+                        vec![field.clone()],
+                        format!("{} must match {}", field, other),
+                    )
+                }
+                CrossFieldRule::ExactlyOne { fields, .. } => {
+                    let fields: Vec<String> = fields.iter().map(key).collect();
+                    (
+                        format!("{} !== 1", Self::count_set_fields(&fields)),
+                        fields.clone(),
+                        format!("Exactly one of {} must be set", fields.join(", ")),
+                    )
+                }
+                CrossFieldRule::AtLeastOne { fields, .. } => {
+                    let fields: Vec<String> = fields.iter().map(key).collect();
+                    (
+                        format!("{} === 0", Self::count_set_fields(&fields)),
+                        fields.clone(),
+                        format!("At least one of {} must be set", fields.join(", ")),
+                    )
+                }
+                CrossFieldRule::AtMostOne { fields, .. } => {
+                    let fields: Vec<String> = fields.iter().map(key).collect();
+                    (
+                        format!("{} > 1", Self::count_set_fields(&fields)),
+                        fields.clone(),
+                        format!("At most one of {} may be set", fields.join(", ")),
+                    )
+                }
+            };
+            let message = rule.message().map_or(default_message, str::to_string);
+            
+            writeln!(checks, "  if ({}) {{", condition).unwrap();
+            for path in paths {
+                writeln!(checks, "    ctx.addIssue({{ code: {}, path: [{}], message: {} }});",
+                    self.zod_version.custom_issue_code(), Value::from(path.as_str()),
+                    Value::from(message.as_str())).unwrap();
+            }
+            writeln!(checks, "  }}").unwrap();
+        }
+        
+        if checks.is_empty() {
+            schema
+        } else {
+            self.zod_version.super_refine(&schema, &format!("(data, ctx) => {{\n{}}}", checks))
+        }
+    }
+    
+    /// Expression counting how many of the given fields are set
+    fn count_set_fields(fields: &[String]) -> String {
+        let values: Vec<String> = fields.iter().map(|field| access(field)).collect();
+        format!("[{}].filter((value) => value != null).length", values.join(", "))
+    }
+    
+    /// Value of a field compared by order: timestamps as dates and 64-bit
+    /// integers held in decimal strings as `bigint`s, other values as they are
+    fn ordered_value(&self, field: &Field, value: &str, direction: Direction) -> String {
+        match field.typ {
+            FieldType::MessageOrEnum(ref type_name) if is_timestamp(type_name) => format!("new Date({})", value),
+            FieldType::Int64 | FieldType::UInt64 | FieldType::SInt64 | FieldType::Fixed64 | FieldType::SFixed64
+                if direction == Direction::Wire && self.int64_strategy == Int64Strategy::String => {
+                format!("BigInt({})", value)
+            }
+            _ => value.to_string(),
+        }
+    }
+    
     /// Generate a Zod field definition
-    fn generate_field(&self, field: &Field, field_metadatas: &HashMap<String, ZodMetadata>) -> String {
+    fn generate_field(
+        &self,
+        field: &Field,
+        field_metadatas: &HashMap<String, ZodMetadata>,
+        scope: &TypeScope,
+    ) -> String {
         let mut content = String::new();
         
         // Get metadata for this field if available
         let field_metadata = field_metadatas.get(&field.name).cloned()
             .unwrap_or_default();
         
-        // Add field name
-        write!(content, "  {}: ", field.name).unwrap();
+        // Add documentation and field name
+        write!(content, "{}", self.property_doc(field, &field_metadata, scope)).unwrap();
+        write!(content, "  {}: ", self.field_key(field)).unwrap();
         
-        // Generate the field type
-        let field_type = match &field.typ {
-            FieldType::Double | FieldType::Float => "z.number()".to_string(),
-            FieldType::Int32 | FieldType::Int64 |
-            FieldType::UInt32 | FieldType::UInt64 |
-            FieldType::SInt32 | FieldType::SInt64 |
-            FieldType::Fixed32 | FieldType::Fixed64 |
-            FieldType::SFixed32 | FieldType::SFixed64 => "z.number().int()".to_string(),
-            FieldType::Bool => "z.boolean()".to_string(),
-            FieldType::String => "z.string()".to_string(),
-            FieldType::Bytes => "z.string()".to_string(), // Bytes represented as base64 strings
-            FieldType::MessageOrEnum(ref type_name) => format!("{}", type_name),
-            FieldType::Map(ref key_type, ref value_type) => {
-                // Maps are represented as records
-                format!("z.record({})", self.type_to_zod_type(value_type))
+        // Generate the field type with its constraints
+        let schema = self.field_schema(field, &field_metadata, scope);
+        
+        // Handle optional fields
+        let schema = match self.field_presence(field, &field_metadata, scope) {
+            Presence::Required => schema,
+            Presence::Optional => self.zod_version.wrap(&schema, "optional"),
+            Presence::Default(default) => {
+                self.apply_default(&schema, &default, self.transforms_input(&field.typ, scope.direction))
             }
         };
+        write!(content, "{}", schema).unwrap();
         
-        write!(content, "{}", field_type).unwrap();
+        content
+    }
+    
+    /// Apply a default, given as a literal of the input of the schema
+    ///
+    /// Zod 4 defaults are outputs, so the defaults of schemas that transform
+    /// their input are parsed as prefaults.
+    fn apply_default(&self, schema: &str, default: &str, transformed: bool) -> String {
+        if transformed {
+            self.zod_version.prefault(schema, default)
+        } else {
+            self.zod_version.with_default(schema, default)
+        }
+    }
+    
+    /// Whether the schema of a field type outputs something else than its input:
+    /// domain schemas, and `bigint`s parsed from strings or numbers
+    fn transforms_input(&self, field_type: &FieldType, direction: Direction) -> bool {
+        let value_type = match field_type {
+            FieldType::Map(_, ref value_type) => value_type.as_ref(),
+            other => other,
+        };
+        direction != Direction::Wire || (is_64_bit(value_type) && self.int64_strategy == Int64Strategy::Union)
+    }
+    
+    /// Fully qualified name of a type of this file, resolved from any scope
+    fn absolute_name(&self, full_name: &str, scope: &TypeScope) -> String {
+        format!(".{}", qualified_name(scope.registry.package().unwrap_or(""), full_name))
+    }
+    
+    /// Object key of a field: its proto name, or its JSON name in JSON mapping mode
+    fn field_key(&self, field: &Field) -> String {
+        if self.json_mapping.enabled {
+            json_name(field)
+        } else {
+            field.name.clone()
+        }
+    }
+    
+    /// Object key of a oneof generated as a single field
+    fn oneof_key(&self, oneof: &OneOf) -> String {
+        if self.json_mapping.enabled {
+            lower_camel_case(&oneof.name)
+        } else {
+            oneof.name.clone()
+        }
+    }
+    
+    /// Whether objects keyed by proto field names are accepted as well as JSON names
+    fn accepts_proto_names(&self) -> bool {
+        self.json_mapping.enabled && self.json_mapping.accept_proto_names
+    }
+    
+    /// Whether a field may be omitted, according to its label and the presence strategy
+    ///
+    /// An explicit `optional` annotation (e.g. `false` from a `required`
+    /// validation rule) overrides both. A default from an annotation or a proto2
+    /// `[default = ...]` applies to any field that is not `required`.
+    fn field_presence(&self, field: &Field, field_metadata: &ZodMetadata, scope: &TypeScope) -> Presence {
+        if let Some(optional) = field_metadata.optional {
+            return if optional { Presence::Optional } else { Presence::Required };
+        }
+        
+        let explicit = match field.label {
+            FieldLabel::Required => return Presence::Required,
+            FieldLabel::Optional => true,
+            FieldLabel::Repeated => false,
+            FieldLabel::Implicit => self.is_message_type(&field.typ, scope),
+        };
         
-        // Apply field-level metadata constraints
-        self.apply_metadata_constraints(&mut content, &field_metadata);
+        if let Some(ref default) = field_metadata.default {
+            match self.default_literal(field, default, scope) {
+                Some(literal) => return Presence::Default(literal),
+                None => warn!("Default value {} does not fit field '{}', ignoring it", default, field.name),
+            }
+        }
+        
+        match self.field_presence {
+            PresenceStrategy::Partial if field.label == FieldLabel::Repeated => Presence::Required,
+            PresenceStrategy::Partial => Presence::Optional,
+            _ if explicit => Presence::Optional,
+            PresenceStrategy::Explicit => Presence::Required,
+            PresenceStrategy::ZeroDefaults => self.zero_value(field, scope)
+                .map_or(Presence::Required, Presence::Default),
+        }
+    }
+    
+    /// Whether a field type is a message, which always has explicit presence
+    ///
+    /// Types from files that were not loaded cannot be resolved and are assumed to be messages.
+    fn is_message_type(&self, field_type: &FieldType, scope: &TypeScope) -> bool {
+        match field_type {
+            FieldType::MessageOrEnum(ref type_name) => match scope.resolve(type_name) {
+                Some(full_name) => scope.registry.kind(&full_name) == Some(TypeKind::Message),
+                None => scope.resolve_import(type_name).is_none_or(|imported| imported.kind == TypeKind::Message),
+            },
+            _ => false,
+        }
+    }
+    
+    /// Zero value of a field without presence, as a TypeScript literal
+    fn zero_value(&self, field: &Field, scope: &TypeScope) -> Option<String> {
+        if field.label == FieldLabel::Repeated {
+            return Some("[]".to_string());
+        }
+        
+        let zero = match field.typ {
+            FieldType::Bool => Value::Bool(false),
+            FieldType::String | FieldType::Bytes => Value::from(""),
+            FieldType::Map(..) => Value::Object(Default::default()),
+            // The first value of an enum is its default
+            FieldType::MessageOrEnum(ref type_name) => {
+                Value::from(self.enum_values(type_name, scope)?.first()?.name.as_str())
+            }
+            _ => Value::from(0),
+        };
+        
+        self.literal(&field.typ, &zero, scope)
+    }
+    
+    /// Default value of a field as a TypeScript literal, or `None` if it does not fit the field
+    fn default_literal(&self, field: &Field, value: &Value, scope: &TypeScope) -> Option<String> {
+        if field.label != FieldLabel::Repeated {
+            return self.literal(&field.typ, value, scope);
+        }
+        let elements = value.as_array()?.iter()
+            .map(|element| self.literal(&field.typ, element, scope))
+            .collect::<Option<Vec<_>>>()?;
+        Some(format!("[{}]", elements.join(", ")))
+    }
+    
+    /// A value of a field type as a TypeScript literal of the input of its schema
+    ///
+    /// Values are accepted as annotations and proto2 options spell them: numbers
+    /// may be strings, floats may be `inf` or `nan` and enum values are names or
+    /// numbers. Returns `None` if the value does not fit the type.
+    fn literal(&self, field_type: &FieldType, value: &Value, scope: &TypeScope) -> Option<String> {
+        let text = match value {
+            Value::String(text) => text.clone(),
+            other => other.to_string(),
+        };
+        
+        let literal = match field_type {
+            FieldType::Double | FieldType::Float => match text.as_str() {
+                "inf" | "Infinity" => "Infinity".to_string(),
+                "-inf" | "-Infinity" => "-Infinity".to_string(),
+                "nan" | "NaN" => "NaN".to_string(),
+                _ => text.parse::<f64>().ok()?.to_string(),
+            },
+            FieldType::Int64 | FieldType::UInt64 |
+            FieldType::SInt64 | FieldType::Fixed64 |
+            FieldType::SFixed64 => {
+                let number = text.parse::<i128>().ok()?;
+                match (scope.direction, self.int64_strategy) {
+                    // Only encoders and `bigint` wire schemas take `bigint`s; the
+                    // others parse decimal strings
+                    (Direction::Encode, _) | (Direction::Wire, Int64Strategy::Bigint) => format!("{}n", number),
+                    _ => format!("\"{}\"", number),
+                }
+            }
+            FieldType::Int32 | FieldType::UInt32 |
+            FieldType::SInt32 | FieldType::Fixed32 |
+            FieldType::SFixed32 => text.parse::<i64>().ok()?.to_string(),
+            FieldType::Bool => text.parse::<bool>().ok()?.to_string(),
+            FieldType::String => Value::from(text).to_string(),
+            FieldType::Bytes if scope.direction == Direction::Encode => match text.as_str() {
+                "" => "new Uint8Array()".to_string(),
+                _ => format!("new TextEncoder().encode({})", Value::from(text)),
+            },
+            FieldType::Bytes => Value::from(base64(text.as_bytes())).to_string(),
+            FieldType::Map(_, ref value_type) => {
+                let entries = value.as_object()?.iter()
+                    .map(|(key, value)| {
+                        Some(format!("{}: {}", Value::from(key.as_str()), self.literal(value_type, value, scope)?))
+                    })
+                    .collect::<Option<Vec<_>>>()?;
+                match entries.is_empty() {
+                    true => "{}".to_string(),
+                    false => format!("{{ {} }}", entries.join(", ")),
+                }
+            }
+            FieldType::MessageOrEnum(ref type_name) => match self.enum_values(type_name, scope) {
+                Some(values) => {
+                    let value = values.iter()
+                        .find(|value| value.name == text || enum_number(&value.number).to_string() == text)?;
+                    // Encoders take domain values, which are names
+                    match self.enum_style {
+                        EnumStyle::TsEnum | EnumStyle::ConstObject if scope.direction != Direction::Encode => {
+                            enum_number(&value.number).to_string()
+                        }
+                        _ => format!("\"{}\"", value.name),
+                    }
+                }
+                // Messages take their JSON form
+                None => value.to_string(),
+            },
+        };
+        
+        Some(literal)
+    }
+    
+    /// A message-level default as a TypeScript literal of the input of the message schema
+    ///
+    /// Keys are proto or JSON field names and values are rendered as defaults of
+    /// their fields. Returns `None` if a key is not a field or a value does not fit it.
+    fn message_literal(&self, message: &Message, value: &Value, scope: &TypeScope) -> Option<String> {
+        let entries = value.as_object()?.iter()
+            .map(|(key, value)| {
+                let field = fields(message).find(|field| field.name == *key || json_name(field) == *key)?;
+                Some(format!("{}: {}", self.field_key(field), self.default_literal(field, value, scope)?))
+            })
+            .collect::<Option<Vec<_>>>()?;
+        match entries.is_empty() {
+            true => Some("{}".to_string()),
+            false => Some(format!("{{ {} }}", entries.join(", "))),
+        }
+    }
+    
+    /// Values of an enum type that are generated, or `None` if the type is not a known enum
+    fn enum_values<'a>(&self, type_name: &str, scope: &'a TypeScope) -> Option<Vec<&'a EnumValue>> {
+        let values = match scope.resolve(type_name) {
+            Some(full_name) => scope.registry.enum_values(&full_name)?,
+            None => scope.resolve_import(type_name)?.enum_values.as_slice(),
+        };
+        Some(values.iter().filter(|value| !(self.omit_deprecated && is_deprecated_value(value))).collect())
+    }
+    
+    /// JSDoc of the declarations of a message or enum: its comment, description,
+    /// notes on its members and deprecation
+    ///
+    /// Encoders only carry the deprecation.
+    fn declaration_docs(
+        &self,
+        full_name: &str,
+        metadata: &ZodMetadata,
+        members: &[String],
+        options: &[ProtoOption],
+        encode: bool,
+    ) -> DeclarationDocs {
+        let mut lines = Vec::new();
+        if !encode {
+            lines = self.doc_lines(full_name, metadata.description.as_deref());
+            if !lines.is_empty() && !members.is_empty() {
+                lines.push("");
+            }
+            lines.extend(members.iter().map(String::as_str));
+        }
+        let deprecated = is_deprecated(options);
+        DeclarationDocs::new(jsdoc(&lines, deprecated, ""), deprecated)
+    }
+    
+    /// JSDoc of a field in an object schema or a written out type
+    fn property_doc(&self, field: &Field, metadata: &ZodMetadata, scope: &TypeScope) -> String {
+        let lines = self.doc_lines(&qualified_name(scope.name, &field.name), metadata.description.as_deref());
+        jsdoc(&lines, is_deprecated(&field.options), "  ")
+    }
+    
+    /// Lines of the JSDoc of an element: its proto comment, then its description
+    fn doc_lines<'a>(&'a self, path: &str, description: Option<&'a str>) -> Vec<&'a str> {
+        self.metadata.comments.get(path).map(String::as_str).into_iter()
+            .chain(description)
+            .flat_map(str::lines)
+            .collect()
+    }
+    
+    /// Lines listing the enum values that are commented or deprecated, for
+    /// enums whose values cannot carry their own JSDoc
+    fn member_doc_lines(&self, full_name: &str, values: &[&EnumValue]) -> Vec<String> {
+        let mut lines = Vec::new();
+        for value in values {
+            let comment = self.metadata.comments.get(&qualified_name(full_name, &value.name));
+            let text = match (is_deprecated_value(value), comment) {
+                (true, Some(comment)) => format!("Deprecated. {}", comment),
+                (true, None) => "Deprecated.".to_string(),
+                (false, Some(comment)) => comment.clone(),
+                (false, None) => continue,
+            };
+            
+            // Later lines of a comment continue the list item
+            for (i, line) in text.lines().enumerate() {
+                lines.push(match i {
+                    0 => format!("- `{}`: {}", value.name, line),
+                    _ if line.is_empty() => String::new(),
+                    _ => format!("  {}", line),
+                });
+            }
+        }
+        lines
+    }
+    
+    /// Metadata whose description falls back to the proto comment, with `describe_comments`
+    fn describe_with_comment(&self, mut metadata: ZodMetadata, path: &str) -> ZodMetadata {
+        if self.describe_comments && metadata.description.is_none() {
+            metadata.description = self.metadata.comments.get(path).cloned();
+        }
+        metadata
+    }
+    
+    /// Generate the schema of a field value: its type, constraints and array wrapper
+    fn field_schema(
+        &self,
+        field: &Field,
+        field_metadata: &ZodMetadata,
+        scope: &TypeScope,
+    ) -> String {
+        let mut content = self.type_to_zod_type(&field.typ, scope);
+        
+        // Defaults are applied with the presence of the field, after the array wrapper
+        let path = qualified_name(scope.name, &field.name);
+        let mut field_metadata = self.describe_with_comment(ZodMetadata { default: None, ..field_metadata.clone() }, &path);
+        
+        // Integer bounds are merged with the range of the type
+        if is_integer(&field.typ) {
+            content = self.integer_schema(&field.typ, field_metadata.min.take(), field_metadata.max.take(),
+                scope.direction);
+        }
+        
+        // Apply field-level metadata constraints; bounds are lengths except for numbers
+        let length = matches!(field.typ, FieldType::String | FieldType::Bytes);
+        content = self.apply_metadata_constraints(content, &field_metadata, length);
         
         // Handle repeated fields (arrays)
         if field.label == FieldLabel::Repeated {
-            write!(content, ".array()").unwrap();
+            content = self.zod_version.wrap(&content, "array");
             
             // Apply array-specific constraints if available
             if let Some(ref array_constraints) = field_metadata.array {
                 if array_constraints.contains_key("min") {
                     if let Some(min) = array_constraints.get("min").and_then(|v| v.as_u64()) {
-                        write!(content, ".min({})", min).unwrap();
+                        content = self.zod_version.min(&content, &min.to_string(), true);
                     }
                 }
                 if array_constraints.contains_key("max") {
                     if let Some(max) = array_constraints.get("max").and_then(|v| v.as_u64()) {
-                        write!(content, ".max({})", max).unwrap();
+                        content = self.zod_version.max(&content, &max.to_string(), true);
                     }
                 }
+                if let Some(length) = array_constraints.get("length").and_then(|v| v.as_u64()) {
+                    content = self.zod_version.length(&content, &length.to_string());
+                }
             }
         }
         
-        // Handle optional fields
-        let is_optional = field.label == FieldLabel::Optional || 
-            field_metadata.optional.unwrap_or(false);
-            
-        if is_optional {
-            write!(content, ".optional()").unwrap();
+        if field_metadata.nullable == Some(true) {
+            content = self.zod_version.wrap(&content, "nullable");
         }
         
         content
     }
     
+    /// Generate the members of a oneof as optional keys of the message object
+    ///
+    /// At most one of them may be set; this is checked by an `at_most_one` refinement.
+    fn generate_oneof_keys(
+        &self,
+        content: &mut String,
+        oneof: &OneOf,
+        field_metadatas: &HashMap<String, ZodMetadata>,
+        scope: &TypeScope,
+    ) {
+        for field in &oneof.fields {
+            let field_metadata = field_metadatas.get(&field.name).cloned().unwrap_or_default();
+            write!(content, "{}", self.property_doc(field, &field_metadata, scope)).unwrap();
+            writeln!(content, "  {}: {},", self.field_key(field),
+                self.zod_version.wrap(&self.field_schema(field, &field_metadata, scope), "optional")).unwrap();
+        }
+    }
+    
+    /// Generate a oneof as a protobuf-es style `{ case, value }` discriminated union
+    ///
+    /// An unset oneof is represented as `{ case: undefined }`.
+    fn generate_oneof_discriminated_union(
+        &self,
+        content: &mut String,
+        oneof: &OneOf,
+        field_metadatas: &HashMap<String, ZodMetadata>,
+        scope: &TypeScope,
+    ) {
+        writeln!(content, "  {}: z.discriminatedUnion(\"case\", [", self.oneof_key(oneof)).unwrap();
+        for field in &oneof.fields {
+            let field_metadata = field_metadatas.get(&field.name).cloned().unwrap_or_default();
+            writeln!(content, "    z.object({{ case: z.literal(\"{}\"), value: {} }}),", self.field_key(field),
+                self.field_schema(field, &field_metadata, scope)).unwrap();
+        }
+        writeln!(content, "    z.object({{ case: z.undefined(), value: {} }}),",
+            self.zod_version.wrap("z.undefined()", "optional")).unwrap();
+        writeln!(content, "  ]),").unwrap();
+    }
+    
+    /// Union of the oneof's variants, intersected with the message object
+    ///
+    /// Each variant sets one member and marks the others as absent; a final
+    /// variant leaves all of them unset.
+    fn oneof_union(
+        &self,
+        oneof: &OneOf,
+        field_metadatas: &HashMap<String, ZodMetadata>,
+        scope: &TypeScope,
+    ) -> String {
+        let mut content = String::new();
+        writeln!(content, "z.union([").unwrap();
+        for set_field in oneof.fields.iter().map(Some).chain(std::iter::once(None)) {
+            let members: Vec<String> = oneof.fields.iter()
+                .map(|field| {
+                    if set_field.is_some_and(|set_field| set_field.name == field.name) {
+                        let field_metadata = field_metadatas.get(&field.name).cloned().unwrap_or_default();
+                        format!("{}: {}", self.field_key(field), self.field_schema(field, &field_metadata, scope))
+                    } else {
+                        format!("{}: {}", self.field_key(field), self.zod_version.wrap("z.undefined()", "optional"))
+                    }
+                })
+                .collect();
+            writeln!(content, "  z.object({{ {} }}),", members.join(", ")).unwrap();
+        }
+        write!(content, "])").unwrap();
+        content
+    }
+    
     /// Apply metadata constraints to a Zod schema
-    fn apply_metadata_constraints(&self, content: &mut String, metadata: &ZodMetadata) {
+    ///
+    /// `length` tells whether `min` and `max` bound a length rather than a number.
+    fn apply_metadata_constraints(&self, schema: String, metadata: &ZodMetadata, length: bool) -> String {
+        let mut schema = schema;
+        let formats: Vec<&str> = [(metadata.email, "email"), (metadata.url, "url"), (metadata.uuid, "uuid")]
+            .into_iter()
+            .filter(|(enabled, _)| enabled.unwrap_or(false))
+            .map(|(_, format)| format)
+            .collect();
+        
+        // Top-level string formats of Zod 4 replace `z.string()`, so they come first
+        if self.zod_version != ZodVersion::V3 {
+            for format in &formats {
+                schema = self.zod_version.string_format(&schema, format);
+            }
+        }
+        
         // Apply min/max constraints
         if let Some(min) = metadata.min {
-            write!(content, ".min({})", min).unwrap();
+            schema = self.zod_version.min(&schema, &min.to_string(), length);
         }
         if let Some(max) = metadata.max {
-            write!(content, ".max({})", max).unwrap();
-        }
-        
-        // Apply email validation
-        if metadata.email.unwrap_or(false) {
-            write!(content, ".email()").unwrap();
+            schema = self.zod_version.max(&schema, &max.to_string(), length);
         }
         
-        // Apply URL validation
-        if metadata.url.unwrap_or(false) {
-            write!(content, ".url()").unwrap();
+        // Apply email, URL and UUID validation
+        if self.zod_version == ZodVersion::V3 {
+            for format in &formats {
+                schema = self.zod_version.string_format(&schema, format);
+            }
         }
         
         // Apply regex pattern
         if let Some(ref regex) = metadata.regex {
-            write!(content, ".regex(new RegExp(\"{}\"))", regex).unwrap();
+            schema = self.zod_version.regex(&schema, &format!("new RegExp({})", Value::from(regex.as_str())));
+        }
+        
+        // Apply custom validators
+        schema = self.apply_custom_validators(schema, metadata);
+        
+        // Brand the validated value
+        if let Some(ref brand) = metadata.brand {
+            schema = self.zod_version.brand(&schema, &Value::from(brand.as_str()).to_string());
         }
         
         // Apply description
         if let Some(ref description) = metadata.description {
-            write!(content, ".describe(\"{}\")", description).unwrap();
+            schema = self.zod_version.describe(&schema, &Value::from(description.as_str()).to_string());
         }
         
-        // Apply default value
-        if let Some(ref default) = metadata.default {
-            write!(content, ".default({})", default).unwrap();
+        schema
+    }
+    
+    /// Apply the custom validators referenced by a `custom` annotation
+    ///
+    /// Validators are looked up in the configured registry; unknown names are
+    /// skipped with a warning rather than emitting a reference to an undefined function.
+    fn apply_custom_validators(&self, schema: String, metadata: &ZodMetadata) -> String {
+        let custom = match metadata.custom {
+            Some(ref custom) => custom,
+            None => return schema,
+        };
+        let mut schema = schema;
+        
+        // Sort by name so that output is stable
+        let validators: BTreeMap<&String, &Value> = custom.iter().collect();
+        
+        for (name, options) in validators {
+            if *options == Value::Bool(false) {
+                continue;
+            }
+            
+            let validator = match self.custom_validators.get(name) {
+                Some(validator) => validator,
+                None => {
+                    warn!("No import configured for custom validator '{}', skipping it", name);
+                    continue;
+                }
+            };
+            
+            match validator.refinement {
+                RefinementKind::SuperRefine => {
+                    schema = self.zod_version.super_refine(&schema, name);
+                }
+                RefinementKind::Refine => {
+                    let message = match options {
+                        Value::String(message) => Some(message.as_str()),
+                        Value::Object(options) => options.get("message").and_then(|m| m.as_str()),
+                        _ => None,
+                    }
+                    .or(validator.message.as_deref());
+                    
+                    schema = match message {
+                        Some(message) => self.zod_version.refine(&schema,
+                            &format!("{}, {{ message: {} }}", name, Value::from(message))),
+                        None => self.zod_version.refine(&schema, name),
+                    };
+                }
+            }
         }
+        
+        schema
     }
     
     /// Convert a FieldType to its corresponding Zod type
-    fn type_to_zod_type(&self, field_type: &FieldType) -> String {
+    ///
+    /// Message and enum references are resolved from the message declaring the
+    /// field; references within a cycle are deferred with `z.lazy`.
+    fn type_to_zod_type(&self, field_type: &FieldType, scope: &TypeScope) -> String {
         match field_type {
             FieldType::Double | FieldType::Float => "z.number()".to_string(),
             FieldType::Int32 | FieldType::Int64 |
             FieldType::UInt32 | FieldType::UInt64 |
             FieldType::SInt32 | FieldType::SInt64 |
             FieldType::Fixed32 | FieldType::Fixed64 |
-            FieldType::SFixed32 | FieldType::SFixed64 => "z.number().int()".to_string(),
+            FieldType::SFixed32 | FieldType::SFixed64 => self.integer_schema(field_type, None, None, scope.direction),
             FieldType::Bool => "z.boolean()".to_string(),
             FieldType::String => "z.string()".to_string(),
+            FieldType::Bytes if scope.direction == Direction::Decode => self.zod_version.transform(
+                &self.zod_version.regex("z.string()", BASE64_PATTERN), BYTES_FROM_BASE64),
+            FieldType::Bytes if scope.direction == Direction::Encode => {
+                self.zod_version.transform("z.instanceof(Uint8Array)", BYTES_TO_BASE64)
+            }
+            FieldType::Bytes if self.json_mapping.enabled => self.zod_version.regex("z.string()", BASE64_PATTERN),
             FieldType::Bytes => "z.string()".to_string(), // Bytes represented as base64 strings
-            FieldType::MessageOrEnum(ref type_name) => type_name.clone(),
-            FieldType::Map(ref _key_type, ref value_type) => {
-                // Maps are represented as records
-                format!("z.record({})", self.type_to_zod_type(value_type))
+            FieldType::MessageOrEnum(ref type_name) => match scope.resolve(type_name) {
+                Some(full_name) if scope.graph.needs_lazy(scope.name, &full_name) => {
+                    format!("z.lazy(() => {})", self.schema_name(&full_name, scope.direction))
+                }
+                Some(full_name) => self.schema_name(&full_name, scope.direction),
+                None => match scope.resolve_import(type_name) {
+                    Some(imported) => {
+                        self.imported_reference(imported, scope.registry, &self.name_suffix(scope.direction))
+                    }
+                    None => match self.well_known_types.schema(type_name, self.zod_version, scope.direction) {
+                        Some(WellKnownSchema::Wrapper(ref scalar)) => self.zod_version.wrap(
+                            &self.type_to_zod_type(scalar, scope),
+                            self.well_known_types.wrapper_modifier()
+                        ),
+                        Some(WellKnownSchema::Schema(schema)) => schema,
+                        None => type_name.clone(),
+                    },
+                },
+            },
+            FieldType::Map(ref key_type, ref value_type) => {
+                // Maps are represented as records; enum and bool keys need not all be present
+                let partial = matches!(**key_type, FieldType::Bool | FieldType::MessageOrEnum(_));
+                self.zod_version.record(&self.map_key_schema(key_type, scope),
+                    &self.type_to_zod_type(value_type, scope), partial)
             }
         }
     }
     
+    /// Zod schema for the keys of a map
+    ///
+    /// JSON object keys are strings, so integer and bool keys are their
+    /// decimal and `"true"`/`"false"` spellings, as in the proto3 JSON mapping.
+    fn map_key_schema(&self, key_type: &FieldType, scope: &TypeScope) -> String {
+        match key_type {
+            FieldType::Int32 | FieldType::Int64 |
+            FieldType::SInt32 | FieldType::SInt64 |
+            FieldType::SFixed32 | FieldType::SFixed64 => self.zod_version.regex("z.string()", r"/^-?\d+$/"),
+            FieldType::UInt32 | FieldType::UInt64 |
+            FieldType::Fixed32 | FieldType::Fixed64 => self.zod_version.regex("z.string()", r"/^\d+$/"),
+            FieldType::Bool => "z.enum([\"true\", \"false\"])".to_string(),
+            FieldType::MessageOrEnum(_) => self.type_to_zod_type(key_type, scope),
+            _ => "z.string()".to_string(),
+        }
+    }
+    
+    /// Zod schema for an integer type, limited to the range of the type
+    ///
+    /// `min` and `max` narrow the range further. 64-bit integers follow the
+    /// configured strategy, as they do not fit in a JavaScript number, except
+    /// in domain schemas, which decode them to `bigint` and encode them as strings.
+    fn integer_schema(&self, field_type: &FieldType, min: Option<i64>, max: Option<i64>, direction: Direction) -> String {
+        let (type_min, type_max, is_64_bit) = match field_type {
+            FieldType::Int32 | FieldType::SInt32 | FieldType::SFixed32 => {
+                (i32::MIN as i128, i32::MAX as i128, false)
+            }
+            FieldType::UInt32 | FieldType::Fixed32 => (0, u32::MAX as i128, false),
+            FieldType::Int64 | FieldType::SInt64 | FieldType::SFixed64 => {
+                (i64::MIN as i128, i64::MAX as i128, true)
+            }
+            _ => (0, u64::MAX as i128, true),
+        };
+        let min = min.map_or(type_min, i128::from).max(type_min);
+        let max = max.map_or(type_max, i128::from).min(type_max);
+        
+        let version = self.zod_version;
+        if !is_64_bit {
+            return version.max(&version.min(version.integer(), &min.to_string(), false), &max.to_string(), false);
+        }
+        
+        let digits = if type_min < 0 { r"/^-?\d+$/" } else { r"/^\d+$/" };
+        let bigint = |schema: &str| version.check(
+            &version.check(schema, "gte", "gte", &format!("{}n", min)), "lte", "lte", &format!("{}n", max));
+        match (direction, self.int64_strategy) {
+            (Direction::Encode, _) => version.transform(&bigint("z.bigint()"), "(value) => value.toString()"),
+            (Direction::Wire, Int64Strategy::String) => version.refine(
+                &version.regex("z.string()", digits),
+                &format!(
+                    "(value) => BigInt(value) >= {0}n && BigInt(value) <= {1}n, {{ message: \"Must be between {0} and {1}\" }}",
+                    min, max
+                ),
+            ),
+            (Direction::Wire, Int64Strategy::Bigint) => bigint("z.bigint()"),
+            (Direction::Decode, _) | (Direction::Wire, Int64Strategy::Union) => version.pipe(
+                &format!("z.union([{}, {}])", version.regex("z.string()", digits), version.integer()),
+                &bigint("z.coerce.bigint()"),
+            ),
+        }
+    }
+    
     /// Get the output filename for a proto file
     fn get_output_filename(&self, proto_file: &ProtoFile) -> String {
+        format!("{}.ts", self.get_output_directory(proto_file))
+    }
+    
+    /// Get the output directory of a proto file in multi-file mode
+    ///
+    /// Named after the path of the proto file when known, so that generated
+    /// files mirror the proto imports; otherwise after the package.
+    fn get_output_directory(&self, proto_file: &ProtoFile) -> String {
+        if let Some(ref source_path) = self.source_path {
+            return module_path(source_path);
+        }
         let package_name = proto_file.package.as_deref().unwrap_or("default");
-        let sanitized_name = package_name.replace('.', "_");
-        format!("{}.ts", sanitized_name)
+        package_name.replace('.', "_")
     }
-}
\ No newline at end of file
+}
+
+/// Expression reading `key` of the object checked by a refinement
+fn access(key: &str) -> String {
+    format!("data[{}]", Value::from(key))
+}
+
+/// Fields of a message, including the members of its oneofs
+fn fields(message: &Message) -> impl Iterator<Item = &Field> {
+    message.fields.iter().chain(message.oneofs.iter().flat_map(|oneof| &oneof.fields))
+}
+
+fn is_64_bit(field_type: &FieldType) -> bool {
+    matches!(
+        field_type,
+        FieldType::Int64 | FieldType::UInt64 |
+        FieldType::SInt64 | FieldType::Fixed64 |
+        FieldType::SFixed64
+    )
+}
+
+/// Whether a field type is an integer
+fn is_integer(field_type: &FieldType) -> bool {
+    matches!(
+        field_type,
+        FieldType::Int32 | FieldType::Int64 |
+        FieldType::UInt32 | FieldType::UInt64 |
+        FieldType::SInt32 | FieldType::SInt64 |
+        FieldType::Fixed32 | FieldType::Fixed64 |
+        FieldType::SFixed32 | FieldType::SFixed64
+    )
+}
+
+/// Whether an enum sets `option allow_alias = true`
+fn allows_alias(enum_def: &Enum) -> bool {
+    enum_def.options.iter()
+        .any(|option| option.name == "allow_alias" && option.value == OptionValue::Bool(true))
+}
+
+/// First component of a package-relative type name, i.e. its top-level type
+fn top_level_name(full_name: &str) -> &str {
+    full_name.split('.').next().unwrap_or(full_name)
+}
+
+/// 64-bit FNV-1a hash of a generated file, for its header
+fn content_hash(content: &str) -> u64 {
+    content.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
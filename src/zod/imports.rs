@@ -0,0 +1,262 @@
+//! Types declared in imported proto files
+//!
+//! A generated file mirrors the path of its proto file (`common/money.proto`
+//! becomes `common/money.ts`), so a type used from an imported proto file is
+//! imported from the generated module of that file.
+
+use crate::parser::ast::{EnumValue, ProtoFile};
+use crate::parser::parse_proto_file;
+use crate::zod::registry::{qualified_name, TypeKind, TypeRegistry};
+use log::warn;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+/// A proto file imported by the file being generated
+#[derive(Debug, Clone)]
+pub struct ImportedFile {
+    /// Import path of the file, e.g. `common/money.proto`
+    pub path: String,
+
+    /// The parsed file
+    pub proto_file: ProtoFile,
+}
+
+/// A message or enum declared in an imported file
+#[derive(Debug, Clone)]
+pub struct ImportedType {
+    /// Module generated for the declaring file, e.g. `common/money`
+    pub module: String,
+
+    /// Package of the declaring file, if any
+    pub package: Option<String>,
+
+    /// Name of the type relative to its package
+    pub name: String,
+
+    /// Kind of the type
+    pub kind: TypeKind,
+
+    /// Values of the type, if it is an enum
+    pub enum_values: Vec<EnumValue>,
+}
+
+/// The types declared in the files imported by a proto file
+#[derive(Debug, Clone, Default)]
+pub struct ImportedTypes {
+    /// Imported types, keyed by their fully qualified name without leading dot
+    types: HashMap<String, ImportedType>,
+}
+
+impl ImportedTypes {
+    /// Collect the types declared in the imported files, including nested ones
+    pub fn new(files: &[ImportedFile]) -> Self {
+        let mut types = HashMap::new();
+
+        for file in files {
+            let registry = TypeRegistry::new(&file.proto_file);
+            let package = file.proto_file.package.clone();
+            for (name, kind) in registry.types() {
+                let imported = ImportedType {
+                    module: module_path(&file.path),
+                    package: package.clone(),
+                    name: name.to_string(),
+                    kind,
+                    enum_values: registry.enum_values(name).map(<[EnumValue]>::to_vec).unwrap_or_default(),
+                };
+                types.insert(qualified_name(package.as_deref().unwrap_or(""), name), imported);
+            }
+        }
+
+        ImportedTypes { types }
+    }
+
+    /// Resolve a type reference made inside `scope` of a file in `package`
+    ///
+    /// As in protobuf, the innermost scope is searched first, then each outer
+    /// scope and package in turn.
+    pub fn resolve(&self, package: Option<&str>, scope: &str, name: &str) -> Option<&ImportedType> {
+        if let Some(absolute) = name.strip_prefix('.') {
+            return self.types.get(absolute);
+        }
+
+        let full_scope = qualified_name(package.unwrap_or(""), scope);
+        let mut scope = full_scope.as_str();
+        loop {
+            if let Some(imported) = self.types.get(&qualified_name(scope, name)) {
+                return Some(imported);
+            }
+            if scope.is_empty() {
+                return None;
+            }
+            scope = scope.rsplit_once('.').map_or("", |(outer, _)| outer);
+        }
+    }
+
+    /// Whether top-level types of the same name are imported from several modules
+    pub fn is_ambiguous(&self, imported: &ImportedType) -> bool {
+        let name = top_level(&imported.name);
+        self.types.values()
+            .any(|other| other.module != imported.module && top_level(&other.name) == name)
+    }
+}
+
+/// First component of a package-relative type name
+fn top_level(name: &str) -> &str {
+    name.split('.').next().unwrap_or(name)
+}
+
+/// Module path of the file generated for a proto file (`common/money.proto` -> `common/money`)
+pub fn module_path(proto_path: &str) -> String {
+    let path = proto_path.trim_start_matches("./");
+    path.strip_suffix(".proto").unwrap_or(path).to_string()
+}
+
+/// Relative import specifier of `module` from a file in the directory `from_dir`
+///
+/// Both are relative to the output directory, e.g. `../common/money` from
+/// `shop/v1` to `shop/common/money`.
+pub fn relative_module(from_dir: &str, module: &str) -> String {
+    let from: Vec<&str> = from_dir.split('/').filter(|part| !part.is_empty()).collect();
+    let to: Vec<&str> = module.split('/').filter(|part| !part.is_empty()).collect();
+
+    let common = from.iter().zip(&to).take_while(|(a, b)| a == b).count();
+    let ups = from.len() - common;
+    let rest = to[common..].join("/");
+
+    if ups == 0 {
+        format!("./{}", rest)
+    } else {
+        format!("{}{}", "../".repeat(ups), rest)
+    }
+}
+
+/// Path of a proto file relative to the include path containing it, e.g.
+/// `shop/order.proto` for `./shop/order.proto` under `.`
+///
+/// Relative paths are taken from `cwd`, and `.` and `..` components are
+/// resolved before comparing. As with `protoc`, a file outside all include
+/// paths is an error.
+pub fn source_path(proto_path: &Path, include_paths: &[PathBuf], cwd: &Path) -> Result<String, String> {
+    let path = normalize(proto_path, cwd);
+    include_paths.iter()
+        .find_map(|include_path| path.strip_prefix(normalize(include_path, cwd)).ok())
+        .map(|relative| {
+            let parts: Vec<_> = relative.components().map(|part| part.as_os_str().to_string_lossy()).collect();
+            parts.join("/")
+        })
+        .ok_or_else(|| format!(
+            "Input file {} is not inside any of the proto paths ({})",
+            proto_path.display(),
+            include_paths.iter().map(|path| path.display().to_string()).collect::<Vec<_>>().join(", "),
+        ))
+}
+
+/// Absolute form of `path` relative to `cwd`, without `.` and `..` components
+fn normalize(path: &Path, cwd: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in cwd.join(path).components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            other => normalized.push(other),
+        }
+    }
+    normalized
+}
+
+/// Read and parse the files imported by a proto file from the include paths
+///
+/// The well-known types are mapped by the generator and not read. Files that
+/// cannot be found or parsed are skipped with a warning, and their types are
+/// left unresolved.
+pub fn load_imports(proto_file: &ProtoFile, include_paths: &[PathBuf]) -> Vec<ImportedFile> {
+    let mut files = Vec::new();
+
+    for import in &proto_file.imports {
+        if import.path.starts_with("google/protobuf/") {
+            continue;
+        }
+
+        let found = include_paths.iter()
+            .map(|include_path| include_path.join(&import.path))
+            .find(|path| path.is_file());
+        let Some(path) = found else {
+            warn!("Imported file {} not found in the proto paths", import.path);
+            continue;
+        };
+
+        let parsed = fs::read_to_string(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|content| parse_proto_file(&content).map_err(|e| e.to_string()));
+        match parsed {
+            Ok(imported) => files.push(ImportedFile { path: import.path.clone(), proto_file: imported }),
+            Err(e) => warn!("Failed to load imported file {}: {}", path.display(), e),
+        }
+    }
+
+    files
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_imported_types() {
+        let money = parse_proto_file(
+            r#"
+            syntax = "proto3";
+            package shop.common;
+            message Money {
+                enum Currency { USD = 0; EUR = 1; }
+                int64 units = 1;
+            }
+            "#,
+        )
+        .unwrap();
+        let imports = ImportedTypes::new(&[ImportedFile {
+            path: "shop/common/money.proto".to_string(),
+            proto_file: money,
+        }]);
+
+        let resolve = |scope, name| {
+            imports.resolve(Some("shop.v1"), scope, name).map(|imported| imported.name.clone())
+        };
+        assert_eq!(resolve("Order", "common.Money"), Some("Money".to_string()));
+        assert_eq!(resolve("", "shop.common.Money.Currency"), Some("Money.Currency".to_string()));
+        assert_eq!(resolve("", ".shop.common.Money"), Some("Money".to_string()));
+        assert_eq!(resolve("", "Money"), None);
+
+        let currency = imports.resolve(None, "", "shop.common.Money.Currency").unwrap();
+        assert_eq!(currency.module, "shop/common/money");
+        assert_eq!(currency.kind, TypeKind::Enum);
+        assert_eq!(currency.enum_values.len(), 2);
+    }
+
+    #[test]
+    fn test_relative_modules() {
+        assert_eq!(module_path("common/money.proto"), "common/money");
+        assert_eq!(relative_module("", "common/money"), "./common/money");
+        assert_eq!(relative_module("shop/v1", "shop/v1/item"), "./item");
+        assert_eq!(relative_module("shop/v1", "shop/common/money"), "../common/money");
+        assert_eq!(relative_module("shop/v1/order", "common/money"), "../../../common/money");
+    }
+
+    #[test]
+    fn test_source_paths() {
+        let cwd = Path::new("/work");
+        let source = |path: &str, include_paths: &[&str]| {
+            let include_paths: Vec<PathBuf> = include_paths.iter().map(PathBuf::from).collect();
+            source_path(Path::new(path), &include_paths, cwd)
+        };
+        assert_eq!(source("shop/order.proto", &["."]), Ok("shop/order.proto".to_string()));
+        assert_eq!(source("./shop/order.proto", &["."]), Ok("shop/order.proto".to_string()));
+        assert_eq!(source("/work/shop/../shop/order.proto", &["./"]), Ok("shop/order.proto".to_string()));
+        assert_eq!(source("shop/order.proto", &["common", "shop"]), Ok("order.proto".to_string()));
+        assert_eq!(source("../other/shop/order.proto", &["/other"]), Ok("shop/order.proto".to_string()));
+        assert!(source("shop/order.proto", &["common"]).unwrap_err().contains("not inside any of the proto paths"));
+    }
+}
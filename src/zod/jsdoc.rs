@@ -0,0 +1,79 @@
+//! JSDoc comments of the generated schemas, properties and enum members
+//!
+//! Descriptions are written as JSDoc blocks, which editors show on hover, and
+//! elements marked `[deprecated = true]` (or `option deprecated = true;`) get a
+//! `@deprecated` tag, which editors strike through.
+
+use crate::parser::ast::{EnumValue, EnumValueOptionValue, OptionValue, ProtoOption};
+use std::fmt::Write;
+
+/// Whether an element is marked deprecated by its options
+pub fn is_deprecated(options: &[ProtoOption]) -> bool {
+    options.iter().any(|option| option.name == "deprecated" && option.value == OptionValue::Bool(true))
+}
+
+/// Whether an enum value is marked deprecated
+pub fn is_deprecated_value(value: &EnumValue) -> bool {
+    value.options.iter()
+        .any(|option| option.name == "deprecated" && option.value == EnumValueOptionValue::Bool(true))
+}
+
+/// JSDoc block with the given lines and an optional `@deprecated` tag, each line
+/// prefixed with `indent`; empty if there is nothing to document
+pub fn jsdoc(lines: &[&str], deprecated: bool, indent: &str) -> String {
+    if lines.is_empty() {
+        return if deprecated { format!("{}/** @deprecated */\n", indent) } else { String::new() };
+    }
+
+    let mut block = String::new();
+    writeln!(block, "{}/**", indent).unwrap();
+    for line in lines {
+        // A `*/` in the text would end the comment early
+        match line.replace("*/", "*\\/") {
+            line if line.is_empty() => writeln!(block, "{} *", indent).unwrap(),
+            line => writeln!(block, "{} * {}", indent, line).unwrap(),
+        }
+    }
+    if deprecated {
+        writeln!(block, "{} * @deprecated", indent).unwrap();
+    }
+    writeln!(block, "{} */", indent).unwrap();
+    block
+}
+
+/// JSDoc of the successive declarations of one element, e.g. a type and its schema
+///
+/// The first declaration gets the whole comment and the following ones only the
+/// `@deprecated` tag, so that every use of a deprecated element is flagged.
+pub struct DeclarationDocs {
+    next: String,
+    deprecated: bool,
+}
+
+impl DeclarationDocs {
+    pub fn new(comment: String, deprecated: bool) -> Self {
+        DeclarationDocs { next: comment, deprecated }
+    }
+
+    /// JSDoc of the next declaration
+    pub fn take(&mut self) -> String {
+        std::mem::replace(&mut self.next, jsdoc(&[], self.deprecated, ""))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_jsdoc() {
+        assert_eq!(jsdoc(&[], false, ""), "");
+        assert_eq!(jsdoc(&[], true, "  "), "  /** @deprecated */\n");
+        assert_eq!(jsdoc(&["A user", "", "Ends */ here"], true, ""),
+            "/**\n * A user\n *\n * Ends *\\/ here\n * @deprecated\n */\n");
+
+        let mut docs = DeclarationDocs::new(jsdoc(&["A user"], true, ""), true);
+        assert_eq!(docs.take(), "/**\n * A user\n * @deprecated\n */\n");
+        assert_eq!(docs.take(), "/** @deprecated */\n");
+    }
+}
@@ -0,0 +1,152 @@
+//! Proto3 JSON mapping of field names and enum values
+//!
+//! In the [proto3 JSON representation](https://protobuf.dev/programming-guides/json/)
+//! fields are keyed by their `json_name` (lowerCamelCase by default), enums may
+//! be sent as names or numbers and `bytes` are base64 strings.
+
+use crate::parser::ast::{Field, Message, NumberValue, OptionValue};
+
+/// Name of the helper function renaming proto field names to JSON names
+pub const PROTO_NAME_ALIASES: &str = "acceptProtoNames";
+
+/// Name of the module holding the proto name helper in multi-file output
+pub const PROTO_NAMES_MODULE: &str = "protoNames";
+
+/// Base64 (standard or URL-safe, with or without padding), as accepted by protobuf parsers
+pub const BASE64_PATTERN: &str = "/^[A-Za-z0-9+/_-]*={0,2}$/";
+
+/// Standard base64 with padding, the encoding of `bytes` in proto3 JSON
+pub fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let group = chunk.iter().enumerate().fold(0u32, |group, (i, &byte)| group | (byte as u32) << (16 - 8 * i));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                encoded.push(ALPHABET[(group >> (18 - 6 * i) & 0x3f) as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}
+
+/// JSON name of a field: its `json_name` option, or its name in lowerCamelCase
+pub fn json_name(field: &Field) -> String {
+    field.options.iter()
+        .find(|option| option.name == "json_name")
+        .and_then(|option| match option.value {
+            OptionValue::String(ref name) => Some(name.clone()),
+            _ => None,
+        })
+        .unwrap_or_else(|| lower_camel_case(&field.name))
+}
+
+/// Convert a field name to lowerCamelCase the way protoc does: underscores are
+/// dropped and the following letter is capitalized
+pub fn lower_camel_case(name: &str) -> String {
+    let mut result = String::with_capacity(name.len());
+    let mut capitalize_next = false;
+    for c in name.chars() {
+        if c == '_' {
+            capitalize_next = true;
+        } else if capitalize_next {
+            result.extend(c.to_uppercase());
+            capitalize_next = false;
+        } else {
+            result.push(c);
+        }
+    }
+    result
+}
+
+/// Fields of a message whose JSON name differs from the proto name, as `(proto, json)` pairs
+pub fn renamed_fields(message: &Message) -> Vec<(String, String)> {
+    let oneof_fields = message.oneofs.iter().flat_map(|oneof| &oneof.fields);
+    message.fields.iter()
+        .chain(oneof_fields)
+        .map(|field| (field.name.clone(), json_name(field)))
+        .filter(|(proto, json)| proto != json)
+        .collect()
+}
+
+/// Whether any of the messages, including nested ones, has a renamed field
+pub fn has_renamed_fields(messages: &[Message]) -> bool {
+    messages.iter().any(|message| {
+        !renamed_fields(message).is_empty() || has_renamed_fields(&message.nested_messages)
+    })
+}
+
+/// Numeric value of an enum value
+pub fn enum_number(number: &NumberValue) -> i64 {
+    match *number {
+        NumberValue::DecimalInt(n) | NumberValue::Octal(n) | NumberValue::Hex(n) => n,
+        NumberValue::Float(f) => f as i64,
+    }
+}
+
+/// Helper function for `z.preprocess` that moves values keyed by proto field
+/// names to their JSON names
+pub fn proto_name_aliases_helper() -> String {
+    format!(
+        concat!(
+            "function {}(names: Record<string, string>) {{\n",
+            "  return (value: unknown) => {{\n",
+            "    if (typeof value !== \"object\" || value === null || Array.isArray(value)) {{\n",
+            "      return value;\n",
+            "    }}\n",
+            "    const result: Record<string, unknown> = {{ ...value }};\n",
+            "    for (const [protoName, jsonName] of Object.entries(names)) {{\n",
+            "      if (protoName in result) {{\n",
+            "        if (!(jsonName in result)) result[jsonName] = result[protoName];\n",
+            "        delete result[protoName];\n",
+            "      }}\n",
+            "    }}\n",
+            "    return result;\n",
+            "  }};\n",
+            "}}",
+        ),
+        PROTO_NAME_ALIASES
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_proto_file;
+
+    #[test]
+    fn test_json_names() {
+        assert_eq!(lower_camel_case("zip_code"), "zipCode");
+        assert_eq!(lower_camel_case("address_line_2"), "addressLine2");
+        assert_eq!(lower_camel_case("name"), "name");
+
+        let proto_file = parse_proto_file(
+            r#"
+            syntax = "proto3";
+            message Address {
+                string zip_code = 1;
+                string street_name = 2 [json_name = "street"];
+                string city = 3;
+            }
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            renamed_fields(&proto_file.messages[0]),
+            vec![
+                ("zip_code".to_string(), "zipCode".to_string()),
+                ("street_name".to_string(), "street".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_base64() {
+        assert_eq!(base64(b""), "");
+        assert_eq!(base64(b"f"), "Zg==");
+        assert_eq!(base64(b"fo"), "Zm8=");
+        assert_eq!(base64(b"foobar"), "Zm9vYmFy");
+    }
+}
@@ -0,0 +1,1243 @@
+//! Layout of generated TypeScript following Prettier's defaults
+//!
+//! The generator writes each statement, object property and import on one
+//! line, and object literals it wants expanded over several lines. Statements
+//! wider than Prettier's print width are broken the way Prettier breaks them:
+//! call chains one call per line, and call arguments, arrays and objects one
+//! item per line, hugging a last object, array or function argument. Objects
+//! written over several lines stay expanded, as Prettier keeps them. Blank
+//! lines are collapsed as Prettier does, so that formatting the generated
+//! files leaves them unchanged.
+
+use lazy_static::lazy_static;
+use regex::Regex;
+
+/// Prettier's default print width
+pub const PRINT_WIDTH: usize = 80;
+
+/// Widths of call arguments short enough not to be worth breaking a line for
+const SHORT_ARGUMENT_WIDTH: usize = PRINT_WIDTH / 4;
+
+lazy_static! {
+    static ref IMPORT: Regex = Regex::new(r#"^import (type )?\{ (.+) \} from ("[^"]*");$"#).unwrap();
+    static ref TYPE_ALIAS: Regex = Regex::new(r"^((?:export )?type [\w$]+ =) (.+);$").unwrap();
+    static ref GENERIC: Regex = Regex::new(r"^([\w$.]+)<(.+)>$").unwrap();
+    static ref IF: Regex = Regex::new(r"^if \((.+)\) \{$").unwrap();
+    static ref DECLARATION: Regex = Regex::new(r"(?s)^((?:export )?(?:const|let) [\w$]+(?::[^=]*)? =) (.+);$").unwrap();
+    static ref PROPERTY: Regex = Regex::new(r#"(?s)^([\w$]+|"[^"]*"): (.+),$"#).unwrap();
+    static ref STATEMENT: Regex = Regex::new(r"(?s)^([\w$]+[.(].*);$").unwrap();
+}
+
+/// Keywords starting statements that are not expression statements
+const KEYWORDS: &[&str] = &[
+    "const", "let", "var", "return", "throw", "yield", "export", "import", "type", "if", "for", "while",
+];
+
+/// Lay out a generated file, ending it with a single newline
+pub fn layout(content: &str) -> String {
+    let content = normalize_quotes(content);
+    let lines: Vec<&str> = content.lines().collect();
+    let laid_out = layout_lines(&lines);
+    if laid_out.is_empty() {
+        return String::new();
+    }
+    let mut out = laid_out.join("\n");
+    out.push('\n');
+    out
+}
+
+/// Quote string literals as Prettier does
+///
+/// Strings are double quoted unless they hold more double quotes than
+/// single quotes, with escapes of the quote that is not used dropped.
+fn normalize_quotes(content: &str) -> String {
+    let bytes = content.as_bytes();
+    let mut out = String::with_capacity(content.len());
+    let mut copied = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        match skip_token(bytes, i) {
+            Some(Some(end)) => {
+                if matches!(bytes[i], b'"' | b'\'') {
+                    out.push_str(&content[copied..i]);
+                    out.push_str(&requote(&content[i + 1..end - 1]));
+                    copied = end;
+                }
+                i = end;
+            }
+            Some(None) => break,
+            None => i += 1,
+        }
+    }
+    out.push_str(&content[copied..]);
+    out
+}
+
+/// A string literal holding the raw `contents` of a quoted one, in the quotes Prettier prefers
+fn requote(contents: &str) -> String {
+    let doubles = contents.matches('"').count();
+    let singles = contents.matches('\'').count();
+    let (quote, other) = if doubles > singles { ('\'', '"') } else { ('"', '\'') };
+    let mut out = String::with_capacity(contents.len() + 2);
+    out.push(quote);
+    let mut chars = contents.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                match chars.next() {
+                    Some(escaped) if escaped == other => out.push(escaped),
+                    escaped => {
+                        out.push('\\');
+                        out.extend(escaped);
+                    }
+                }
+            }
+            _ if c == quote => {
+                out.push('\\');
+                out.push(c);
+            }
+            _ => out.push(c),
+        }
+    }
+    out.push(quote);
+    out
+}
+
+/// Lay out a sequence of statements or object properties
+///
+/// Runs of blank lines become one blank line, and there are none at the
+/// start or end of the sequence, nor at the start or end of a block.
+fn layout_lines(lines: &[&str]) -> Vec<String> {
+    let mut out: Vec<String> = Vec::new();
+    let mut pending_blank = false;
+    let mut start = 0;
+    while start < lines.len() {
+        let line = lines[start].trim_end();
+        if line.is_empty() {
+            pending_blank = true;
+            start += 1;
+            continue;
+        }
+        let opens_block = out.last().is_some_and(|last| last.ends_with(['{', '[', '(']));
+        let closes_block = line.trim_start().starts_with(['}', ']', ')']);
+        if pending_blank && !out.is_empty() && !opens_block && !closes_block {
+            out.push(String::new());
+        }
+        pending_blank = false;
+
+        // A statement runs until the brackets it opens are closed
+        let mut end = start + 1;
+        let mut depth = depth_change(line);
+        while depth > 0 && end < lines.len() {
+            depth += depth_change(lines[end]);
+            end += 1;
+        }
+        let statement: Vec<&str> = lines[start..end].iter().map(|line| line.trim_end()).collect();
+        out.extend(layout_statement(&statement));
+        start = end;
+    }
+    out
+}
+
+/// Lay out one statement or property, possibly written over several lines
+fn layout_statement(lines: &[&str]) -> Vec<String> {
+    let first = lines[0];
+    let body = first.trim_start();
+    if is_comment(body) {
+        return lines.iter().map(|line| line.to_string()).collect();
+    }
+    let indent = first.len() - body.len();
+    if lines.len() == 1 {
+        if let Some(printed) = print_single_line(body, indent) {
+            return printed.lines().map(String::from).collect();
+        }
+    }
+    let text = lines.join("\n");
+    if let Some(printed) = print_expression_statement(text.trim_start(), indent) {
+        return printed.lines().map(String::from).collect();
+    }
+
+    // Other blocks keep their first and last lines, with their contents laid out
+    let last = lines[lines.len() - 1];
+    if lines.len() > 1 && first.ends_with(['{', '(', '[']) && last.trim_start().starts_with(['}', ')', ']']) {
+        let mut out = layout_statement(&lines[..1]);
+        out.extend(layout_lines(&lines[1..lines.len() - 1]));
+        out.push(last.to_string());
+        return out;
+    }
+    lines.iter().map(|line| line.to_string()).collect()
+}
+
+/// Lay out the statements that only ever take one line as written: imports,
+/// type aliases and the heads of `if` statements
+fn print_single_line(body: &str, indent: usize) -> Option<String> {
+    let pad = " ".repeat(indent);
+    let fits_line = indent + width(body) <= PRINT_WIDTH;
+
+    if let Some(caps) = IF.captures(body) {
+        let condition = &caps[1];
+        if fits_line || !is_balanced(condition) {
+            return Some(format!("{}{}", pad, body));
+        }
+        let condition = print_operands(condition, indent + 2, 0).unwrap_or_else(|| condition.to_string());
+        return Some(format!("{}if (\n{}  {}\n{}) {{", pad, pad, condition, pad));
+    }
+    if !is_balanced(body) {
+        return Some(format!("{}{}", pad, body));
+    }
+    if let Some(caps) = IMPORT.captures(body) {
+        if fits_line {
+            return Some(format!("{}{}", pad, body));
+        }
+        let type_only = caps.get(1).map_or("", |m| m.as_str());
+        let names: String = split_top(&caps[2], b',').iter()
+            .map(|name| format!("{}  {},\n", pad, name))
+            .collect();
+        return Some(format!("{}import {}{{\n{}{}}} from {};", pad, type_only, names, pad, &caps[3]));
+    }
+    if let Some(caps) = TYPE_ALIAS.captures(body) {
+        if fits_line {
+            return Some(format!("{}{}", pad, body));
+        }
+        let members = split_top(&caps[2], b'|');
+        if members.len() > 1 {
+            let members: String = members.iter().map(|member| format!("\n{}  | {}", pad, member)).collect();
+            return Some(format!("{}{}{};", pad, &caps[1], members));
+        }
+        // Type arguments break instead, without a trailing comma
+        return GENERIC.captures(&caps[2]).map(|generic| {
+            format!("{}{} {}<\n{}  {}\n{}>;", pad, &caps[1], &generic[1], pad, &generic[2], pad)
+        });
+    }
+    None
+}
+
+/// Lay out a declaration, object property or expression statement
+fn print_expression_statement(body: &str, indent: usize) -> Option<String> {
+    let pad = " ".repeat(indent);
+    if let Some(caps) = DECLARATION.captures(body) {
+        let value = parse(&caps[2]);
+        if value.is_opaque() {
+            return None;
+        }
+        return Some(format!("{}{};", pad, print_assignment(&caps[1], &value, indent, 1, false)));
+    }
+    if let Some(caps) = PROPERTY.captures(body) {
+        let value = parse(&caps[2]);
+        if value.is_opaque() {
+            return None;
+        }
+        // Breaking after very short keys gains too little to be worth it
+        let short_key = width(&caps[1]) < 5;
+        let key = format!("{}:", &caps[1]);
+        return Some(format!("{}{},", pad, print_assignment(&key, &value, indent, 1, short_key)));
+    }
+    if let Some(caps) = STATEMENT.captures(body) {
+        let first_word = body.split(|c: char| !c.is_alphanumeric() && c != '_' && c != '$').next().unwrap_or("");
+        let value = parse(&caps[1]);
+        if KEYWORDS.contains(&first_word) || value.is_opaque() {
+            return None;
+        }
+        return Some(format!("{}{};", pad, print_expr(&value, indent, indent, 1, true)));
+    }
+    None
+}
+
+/// `left` (a declaration or object key, with its `=` or `:`) followed by `value`
+///
+/// Strings and chains that have nowhere good to break move to the next line
+/// when they do not fit; other values start on the line of `left`.
+fn print_assignment(left: &str, value: &Expr, indent: usize, tail: usize, short_key: bool) -> String {
+    let col = indent + width(left) + 1;
+    let breaks_after = !short_key && value.is_poorly_breakable();
+    if breaks_after && !fits(col, &value.flat(), tail) {
+        let printed = print_expr(value, indent + 2, indent + 2, tail, false);
+        return format!("{}\n{}{}", left, " ".repeat(indent + 2), printed);
+    }
+    format!("{} {}", left, print_expr(value, col, indent, tail, false))
+}
+
+/// Expression as far as its layout is concerned
+#[derive(Debug, Clone, PartialEq)]
+enum Expr {
+    /// Anything laid out as written: literals, identifiers, arrow functions, operators
+    Atom(String),
+
+    /// Object literal written on one line
+    Object(Vec<Property>),
+
+    /// Object literal written over several lines, which stays expanded
+    Block(Vec<String>),
+
+    /// Arrow function with a block body, written over several lines
+    Function { head: String, body: Vec<String> },
+
+    /// Array literal
+    Array(Vec<Expr>),
+
+    /// Identifier followed by member accesses and calls, at least one of them a call
+    Chain { head: String, links: Vec<Link> },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Property {
+    key: String,
+
+    /// Value, absent for shorthand properties and spreads
+    value: Option<Expr>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Link {
+    /// `.name`, `?.name` or `[index]`
+    Member(String),
+
+    /// Call with its arguments
+    Call(Vec<Expr>),
+}
+
+impl Expr {
+    /// The expression on one line, up to its first forced line break
+    fn flat(&self) -> String {
+        match self {
+            Expr::Atom(text) => text.clone(),
+            Expr::Object(properties) if properties.is_empty() => "{}".to_string(),
+            Expr::Object(properties) => {
+                let properties: Vec<String> = properties.iter().map(Property::flat).collect();
+                format!("{{ {} }}", properties.join(", "))
+            }
+            Expr::Block(_) => "{\n}".to_string(),
+            Expr::Function { head, .. } => format!("{} {{\n}}", head),
+            Expr::Array(elements) => format!("[{}]", flat_list(elements)),
+            Expr::Chain { head, links } => format!("{}{}", head, flat_links(links)),
+        }
+    }
+
+    /// Whether the expression holds text spread over several lines in a way
+    /// this layout does not understand
+    fn is_opaque(&self) -> bool {
+        match self {
+            Expr::Atom(text) => text.contains('\n'),
+            Expr::Object(properties) => properties.iter().any(|property| property.value.as_ref().is_some_and(Expr::is_opaque)),
+            Expr::Block(_) | Expr::Function { .. } => false,
+            Expr::Array(elements) => elements.iter().any(Expr::is_opaque),
+            Expr::Chain { links, .. } => links.iter().any(|link| match link {
+                Link::Member(_) => false,
+                Link::Call(args) => args.iter().any(Expr::is_opaque),
+            }),
+        }
+    }
+
+    /// Whether the expression always spreads over several lines
+    fn will_break(&self) -> bool {
+        match self {
+            Expr::Atom(_) => false,
+            Expr::Block(_) | Expr::Function { .. } => true,
+            Expr::Object(properties) => properties.iter().any(|property| property.value.as_ref().is_some_and(Expr::will_break)),
+            Expr::Array(elements) => elements.iter().any(Expr::will_break),
+            Expr::Chain { links, .. } => links_will_break(links),
+        }
+    }
+
+    /// Prettier's `isSimpleCallArgument`, deciding whether a call chain may stay on one line
+    fn is_simple(&self) -> bool {
+        match self {
+            Expr::Atom(text) => is_simple_atom(text),
+            Expr::Object(properties) => properties.iter()
+                .all(|property| property.value.as_ref().map_or(!property.key.starts_with("..."), Expr::is_simple)),
+            Expr::Block(_) => false,
+            Expr::Function { .. } => true,
+            Expr::Array(elements) => elements.iter().all(Expr::is_simple),
+            Expr::Chain { links, .. } => links.iter().all(|link| match link {
+                Link::Member(member) => !member.starts_with('['),
+                Link::Call(args) => args.iter().all(Expr::is_simple),
+            }),
+        }
+    }
+
+    /// Kind of expression that can be hugged as the last argument of a call
+    fn hug_kind(&self) -> Option<&'static str> {
+        match self {
+            Expr::Object(properties) if !properties.is_empty() => Some("object"),
+            Expr::Block(_) => Some("object"),
+            Expr::Array(elements) if !elements.is_empty() => Some("array"),
+            Expr::Function { .. } => Some("function"),
+            _ => None,
+        }
+    }
+
+    /// Whether the expression is a string literal, or a member chain whose
+    /// calls take at most one short argument, which Prettier prefers to move
+    /// after the `=` or `:` than to break
+    fn is_poorly_breakable(&self) -> bool {
+        match self {
+            Expr::Atom(text) => is_string(text) || (text.contains('.') && is_path(text)),
+            Expr::Chain { head, links } => {
+                let shape = ChainShape::new(head, links, false);
+                shape.groups.len() < shape.cutoff() && links.iter().all(|link| match link {
+                    Link::Member(_) => true,
+                    Link::Call(args) => match args.as_slice() {
+                        [] => true,
+                        [Expr::Atom(arg)] => is_short_argument(arg),
+                        _ => false,
+                    },
+                })
+            }
+            _ => false,
+        }
+    }
+}
+
+impl Property {
+    fn flat(&self) -> String {
+        match self.value {
+            Some(ref value) => format!("{}: {}", self.key, value.flat()),
+            None => self.key.clone(),
+        }
+    }
+}
+
+fn flat_list(exprs: &[Expr]) -> String {
+    exprs.iter().map(Expr::flat).collect::<Vec<_>>().join(", ")
+}
+
+fn flat_links(links: &[Link]) -> String {
+    links.iter()
+        .map(|link| match link {
+            Link::Member(member) => member.clone(),
+            Link::Call(args) => format!("({})", flat_list(args)),
+        })
+        .collect()
+}
+
+fn links_will_break(links: &[Link]) -> bool {
+    links.iter().any(|link| matches!(link, Link::Call(args) if args.iter().any(Expr::will_break)))
+}
+
+fn is_simple_atom(text: &str) -> bool {
+    if let Some(rest) = text.strip_prefix(['-', '+', '!']) {
+        return is_simple_atom(rest);
+    }
+    if let Some(callee) = text.strip_prefix("new ") {
+        return parse(callee).is_simple();
+    }
+    if let Some(body) = arrow_body(text) {
+        return body.starts_with('{');
+    }
+    if is_regex(text) {
+        return is_short_regex(text);
+    }
+    is_string(text) || is_path(text)
+}
+
+/// Prettier's `isLoneShortArgument`
+fn is_short_argument(text: &str) -> bool {
+    if let Some(rest) = text.strip_prefix(['-', '+', '!']) {
+        return is_short_argument(rest);
+    }
+    if is_regex(text) {
+        return is_short_regex(text);
+    }
+    if is_string(text) || is_identifier(text) {
+        return width(text) <= SHORT_ARGUMENT_WIDTH;
+    }
+    text.starts_with(|c: char| c.is_ascii_digit()) || matches!(text, "true" | "false" | "null" | "this" | "{}" | "[]")
+}
+
+fn is_string(text: &str) -> bool {
+    text.starts_with(['"', '\'', '`']) && skip_literal(text.as_bytes(), 0) == Some(text.len())
+}
+
+fn is_regex(text: &str) -> bool {
+    text.starts_with('/') && skip_literal(text.as_bytes(), 0) == Some(text.len())
+}
+
+/// Whether a regular expression literal has a pattern of at most five characters
+fn is_short_regex(text: &str) -> bool {
+    let pattern = text[1..].trim_end_matches(|c: char| c.is_ascii_alphabetic());
+    width(pattern) <= 5 + 1
+}
+
+fn is_identifier(text: &str) -> bool {
+    !text.is_empty() && text.chars().all(|c| c.is_alphanumeric() || c == '_' || c == '$')
+}
+
+/// Identifiers, numbers and member accesses on them
+fn is_path(text: &str) -> bool {
+    !text.is_empty() && text.chars().all(|c| c.is_alphanumeric() || matches!(c, '_' | '$' | '.'))
+}
+
+fn is_comment(text: &str) -> bool {
+    text.starts_with("//") || text.starts_with("/*") || text.starts_with('*')
+}
+
+/// Body of an arrow function with parenthesised parameters
+fn arrow_body(text: &str) -> Option<&str> {
+    if !text.starts_with('(') {
+        return None;
+    }
+    let close = matching(text.as_bytes(), 0)?;
+    text[close + 1..].strip_prefix(" => ")
+}
+
+fn width(text: &str) -> usize {
+    text.chars().count()
+}
+
+/// Column after printing `text` from column `col`
+fn end_col(col: usize, text: &str) -> usize {
+    match text.rsplit_once('\n') {
+        Some((_, last)) => width(last),
+        None => col + width(text),
+    }
+}
+
+/// Whether `text` fits from column `col`, followed by `tail` columns that
+/// cannot be broken; only its first line counts if it spreads over several
+fn fits(col: usize, text: &str, tail: usize) -> bool {
+    match text.split_once('\n') {
+        Some((first, _)) => col + width(first) <= PRINT_WIDTH,
+        None => col + width(text) + tail <= PRINT_WIDTH,
+    }
+}
+
+/// Print an expression starting at column `col` on a line indented by `indent`,
+/// followed on that line by `tail` columns of text that cannot be broken
+fn print_expr(expr: &Expr, col: usize, indent: usize, tail: usize, statement: bool) -> String {
+    match expr {
+        Expr::Atom(text) if fits(col, text, tail) => text.clone(),
+        Expr::Atom(text) => print_arrow(text, indent, tail).unwrap_or_else(|| text.clone()),
+        Expr::Object(_) | Expr::Array(_) if !expr.will_break() && !forces_break(expr) && fits(col, &expr.flat(), tail) => {
+            expr.flat()
+        }
+        Expr::Object(_) | Expr::Array(_) | Expr::Block(_) | Expr::Function { .. } => print_broken(expr, indent),
+        Expr::Chain { head, links } => print_chain(head, links, col, indent, tail, statement),
+    }
+}
+
+/// Arrays of several objects, or of several arrays, with more than one item
+/// each are always expanded
+fn forces_break(expr: &Expr) -> bool {
+    let Expr::Array(elements) = expr else {
+        return false;
+    };
+    elements.len() > 1
+        && (elements.iter().all(|element| matches!(element, Expr::Object(properties) if properties.len() > 1))
+            || elements.iter().all(|element| matches!(element, Expr::Array(items) if items.len() > 1)))
+}
+
+/// Arrow function with a logical expression as its body, the body below the
+/// parameters and broken into one operand per line if it still does not fit
+fn print_arrow(text: &str, indent: usize, tail: usize) -> Option<String> {
+    let body = arrow_body(text)?;
+    let params = &text[..text.len() - body.len() - 4];
+    let pad = " ".repeat(indent + 2);
+    let body = print_operands(body, indent + 2, tail)?;
+    Some(format!("{} =>\n{}{}", params, pad, body))
+}
+
+/// Operands of a logical expression or comparison, on one line if they fit
+/// there and otherwise one per line, each but the last followed by the operator
+fn print_operands(text: &str, indent: usize, tail: usize) -> Option<String> {
+    let (operator, operands) = ["||", "&&", "===", "!==", "==", "!=", "<=", ">=", "<", ">"].iter()
+        .map(|operator| (*operator, split_operator(text, operator)))
+        .find(|(_, operands)| operands.len() > 1)?;
+    // Mixed operators are left as written
+    if operator == "||" && operands.iter().any(|operand| split_operator(operand, "&&").len() > 1) {
+        return None;
+    }
+    if fits(indent, text, tail) {
+        return Some(text.to_string());
+    }
+    Some(operands.join(&format!(" {}\n{}", operator, " ".repeat(indent))))
+}
+
+/// Object, array or function with one item or statement per line
+fn print_broken(expr: &Expr, indent: usize) -> String {
+    let pad = " ".repeat(indent + 2);
+    let items: Vec<String> = match expr {
+        Expr::Object(properties) => properties.iter()
+            .map(|property| match property.value {
+                Some(ref value) => {
+                    let key = format!("{}:", property.key);
+                    let short_key = width(&property.key) < 5;
+                    format!("{}{},", pad, print_assignment(&key, value, indent + 2, 1, short_key))
+                }
+                None => format!("{}{},", pad, property.key),
+            })
+            .collect(),
+        Expr::Array(elements) => elements.iter()
+            .map(|element| format!("{}{},", pad, print_expr(element, indent + 2, indent + 2, 1, false)))
+            .collect(),
+        Expr::Block(lines) => return print_block(lines, indent),
+        Expr::Function { head, body } => return format!("{} {}", head, print_block(body, indent)),
+        _ => return expr.flat(),
+    };
+    let (open, close) = if matches!(expr, Expr::Array(_)) { ("[", "]") } else { ("{", "}") };
+    format!("{}\n{}\n{}{}", open, items.join("\n"), " ".repeat(indent), close)
+}
+
+/// Lines of a block written over several lines, moved to `indent` and laid out
+fn print_block(lines: &[String], indent: usize) -> String {
+    let current = lines.iter()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.len() - line.trim_start().len())
+        .min()
+        .unwrap_or(0);
+    let pad = " ".repeat(indent + 2);
+    let moved: Vec<String> = lines.iter()
+        .map(|line| if line.trim().is_empty() { String::new() } else { format!("{}{}", pad, &line[current..]) })
+        .collect();
+    let moved: Vec<&str> = moved.iter().map(String::as_str).collect();
+    let laid_out = layout_lines(&moved);
+    if laid_out.is_empty() {
+        return "{}".to_string();
+    }
+    format!("{{\n{}\n{}}}", laid_out.join("\n"), " ".repeat(indent))
+}
+
+/// How Prettier groups the links of a member chain
+struct ChainShape<'a> {
+    /// Number of links printed with the head: the calls directly on it and,
+    /// for a plain identifier, the member accesses before the last one
+    first: usize,
+
+    /// The other links, each group starting with member accesses followed by calls
+    groups: Vec<&'a [Link]>,
+
+    /// Whether the first group stays on the line of the head when the chain is expanded
+    merge: bool,
+}
+
+impl<'a> ChainShape<'a> {
+    fn new(head: &str, links: &'a [Link], statement: bool) -> Self {
+        let mut first = 0;
+        while matches!(links.get(first), Some(Link::Call(_))) {
+            first += 1;
+        }
+        while matches!((links.get(first), links.get(first + 1)), (Some(Link::Member(_)), Some(Link::Member(_)))) {
+            first += 1;
+        }
+
+        let mut groups: Vec<&[Link]> = Vec::new();
+        let mut start = first;
+        let mut seen_call = false;
+        for (index, link) in links.iter().enumerate().skip(first) {
+            if seen_call && matches!(link, Link::Member(_)) {
+                groups.push(&links[start..index]);
+                start = index;
+                seen_call = false;
+            }
+            seen_call |= matches!(link, Link::Call(_));
+        }
+        if start < links.len() {
+            groups.push(&links[start..]);
+        }
+
+        // Factories and short names in statements stay with their first call
+        let is_factory = |name: &str| {
+            name.starts_with(|c: char| c.is_ascii_uppercase()) || name.chars().all(|c| c == '_' || c == '$')
+        };
+        let merge = !groups.is_empty()
+            && if first == 0 {
+                head == "this" || is_factory(head) || (statement && width(head) <= 2)
+            } else {
+                matches!(&links[first - 1], Link::Member(member) if is_factory(member.trim_start_matches(['.', '?'])))
+            };
+        ChainShape { first, groups, merge }
+    }
+
+    /// Number of groups, counting the head's, up to which a chain stays on one line
+    fn cutoff(&self) -> usize {
+        if self.merge { 3 } else { 2 }
+    }
+}
+
+/// Prettier's member chain layout
+///
+/// Chains of up to two groups, counting the head's (three when the head is a
+/// capitalised factory), stay on one line, breaking arguments as needed.
+/// Longer chains that do not fit, whose calls take functions or other complex
+/// arguments, or whose groups but the last spread over several lines, get one
+/// call per line.
+fn print_chain(head: &str, links: &[Link], col: usize, indent: usize, tail: usize, statement: bool) -> String {
+    let shape = ChainShape::new(head, links, statement);
+    if shape.groups.len() < shape.cutoff() {
+        return print_links(head, links, col, indent, tail);
+    }
+
+    let calls: Vec<&Vec<Expr>> = links.iter()
+        .filter_map(|link| match link {
+            Link::Call(args) => Some(args),
+            Link::Member(_) => None,
+        })
+        .collect();
+    let complex = calls.len() > 2 && calls.iter().any(|args| !args.iter().all(Expr::is_simple));
+    let (_, others) = shape.groups.split_last().expect("groups");
+    let breaks_early = links_will_break(&links[..shape.first]) || others.iter().any(|group| links_will_break(group));
+    let flat = format!("{}{}", head, flat_links(links));
+    if !complex && !breaks_early && fits(col, &flat, tail) {
+        return print_links(head, links, col, indent, tail);
+    }
+
+    let (merged, rest) = if shape.merge { (shape.groups[0].len(), &shape.groups[1..]) } else { (0, &shape.groups[..]) };
+    let mut out = print_links(head, &links[..shape.first + merged], col, indent, 0);
+    for (index, group) in rest.iter().enumerate() {
+        let group_tail = if index == rest.len() - 1 { tail } else { 0 };
+        out.push('\n');
+        out.push_str(&" ".repeat(indent + 2));
+        out.push_str(&print_links("", group, indent + 2, indent + 2, group_tail));
+    }
+    out
+}
+
+/// Links on one line, breaking the arguments of the calls that do not fit
+fn print_links(head: &str, links: &[Link], col: usize, indent: usize, tail: usize) -> String {
+    let mut out = head.to_string();
+    let mut col = col + width(head);
+    for (index, link) in links.iter().enumerate() {
+        let printed = match link {
+            Link::Member(member) => member.clone(),
+            Link::Call(args) if args.is_empty() => "()".to_string(),
+            Link::Call(args) => {
+                // The line can break again inside the next call taking arguments
+                let mut rest = 0;
+                let mut breaks_later = false;
+                for next in &links[index + 1..] {
+                    match next {
+                        Link::Call(args) if !args.is_empty() => {
+                            rest += 1;
+                            breaks_later = true;
+                            break;
+                        }
+                        next => rest += width(&flat_links(std::slice::from_ref(next))),
+                    }
+                }
+                if !breaks_later {
+                    rest += tail;
+                }
+                let flat = format!("({})", flat_list(args));
+                if !args.iter().any(Expr::will_break) && fits(col, &flat, rest) {
+                    flat
+                } else {
+                    print_args(args, col, indent)
+                }
+            }
+        };
+        col = end_col(col, &printed);
+        out.push_str(&printed);
+    }
+    out
+}
+
+/// Arguments that do not fit on one line: a last object, array or function
+/// is hugged, otherwise each argument goes on its own line
+fn print_args(args: &[Expr], col: usize, indent: usize) -> String {
+    let (last, others) = args.split_last().expect("arguments");
+    let hug_kind = last.hug_kind();
+    let same_kind = others.last().is_some_and(|other| other.hug_kind() == hug_kind);
+    if hug_kind.is_some() && !same_kind && !others.iter().any(Expr::will_break) {
+        let mut head = flat_list(others);
+        if !head.is_empty() {
+            head.push_str(", ");
+        }
+        let opening = match last {
+            Expr::Function { head, .. } => format!("{} {{", head),
+            Expr::Array(_) => "[".to_string(),
+            _ => "{".to_string(),
+        };
+        if fits(col, &format!("({}{}", head, opening), 0) {
+            return format!("({}{})", head, print_broken(last, indent));
+        }
+    }
+    let pad = " ".repeat(indent + 2);
+    let args: String = args.iter()
+        .map(|arg| format!("{}{},\n", pad, print_expr(arg, indent + 2, indent + 2, 1, false)))
+        .collect();
+    format!("(\n{}{})", args, " ".repeat(indent))
+}
+
+fn parse(text: &str) -> Expr {
+    let text = text.trim();
+    let bytes = text.as_bytes();
+    let enclosed = |open: u8| bytes.first() == Some(&open) && matching(bytes, 0) == Some(bytes.len() - 1);
+
+    if enclosed(b'{') {
+        let inner = &text[1..text.len() - 1];
+        if inner.contains('\n') {
+            return Expr::Block(block_lines(inner));
+        }
+        return parse_object(inner).unwrap_or_else(|| Expr::Atom(text.to_string()));
+    }
+    if enclosed(b'[') {
+        return Expr::Array(parse_list(&text[1..text.len() - 1]));
+    }
+    if let Some(body) = arrow_body(text) {
+        let params = &text[..text.len() - body.len() - 4];
+        let body_bytes = body.as_bytes();
+        if body.starts_with('{')
+            && body.contains('\n')
+            && !params.contains('\n')
+            && matching(body_bytes, 0) == Some(body_bytes.len() - 1)
+        {
+            let head = format!("{} =>", params);
+            return Expr::Function { head, body: block_lines(&body[1..body.len() - 1]) };
+        }
+    }
+    parse_chain(text).unwrap_or_else(|| Expr::Atom(text.to_string()))
+}
+
+/// Lines between the braces of a block written over several lines
+fn block_lines(inner: &str) -> Vec<String> {
+    let mut lines: Vec<String> = inner.split('\n').map(|line| line.trim_end().to_string()).collect();
+    // Nothing follows the opening brace on its line, nor precedes the closing one
+    lines.remove(0);
+    lines.pop();
+    lines
+}
+
+/// Comma-separated items, ignoring a trailing comma
+fn parse_list(inner: &str) -> Vec<Expr> {
+    let mut items = split_top(inner, b',');
+    if items.last().is_some_and(|item| item.is_empty()) {
+        items.pop();
+    }
+    items.into_iter().map(parse).collect()
+}
+
+fn parse_object(inner: &str) -> Option<Expr> {
+    let mut properties = Vec::new();
+    if inner.trim().is_empty() {
+        return Some(Expr::Object(properties));
+    }
+    for part in split_top(inner, b',') {
+        match split_top(part, b':').as_slice() {
+            [key] if key.starts_with("...") || is_identifier(key) => {
+                properties.push(Property { key: key.to_string(), value: None });
+            }
+            [key, ..] if is_identifier(key) || is_string(key) => {
+                let value = part[part.find(':')? + 1..].trim();
+                properties.push(Property { key: key.to_string(), value: Some(parse(value)) });
+            }
+            _ => return None,
+        }
+    }
+    Some(Expr::Object(properties))
+}
+
+fn parse_chain(text: &str) -> Option<Expr> {
+    let bytes = text.as_bytes();
+    let identifier_end = |start: usize| {
+        let mut end = start;
+        while end < bytes.len() && (bytes[end].is_ascii_alphanumeric() || matches!(bytes[end], b'_' | b'$')) {
+            end += 1;
+        }
+        end
+    };
+    let head_end = identifier_end(0);
+    if head_end == 0 || bytes[0].is_ascii_digit() {
+        return None;
+    }
+
+    let mut links = Vec::new();
+    let mut i = head_end;
+    while i < bytes.len() {
+        let start = i;
+        match bytes[i] {
+            b'.' | b'?' => {
+                if bytes[i] == b'?' && bytes.get(i + 1) != Some(&b'.') {
+                    return None;
+                }
+                i += if bytes[i] == b'?' { 2 } else { 1 };
+                let end = identifier_end(i);
+                if end == i {
+                    return None;
+                }
+                i = end;
+                links.push(Link::Member(text[start..i].to_string()));
+            }
+            b'(' => {
+                let close = matching(bytes, i)?;
+                links.push(Link::Call(parse_list(&text[i + 1..close])));
+                i = close + 1;
+            }
+            b'[' => {
+                i = matching(bytes, i)? + 1;
+                links.push(Link::Member(text[start..i].to_string()));
+            }
+            _ => return None,
+        }
+    }
+    if !links.iter().any(|link| matches!(link, Link::Call(_))) {
+        return None;
+    }
+    Some(Expr::Chain { head: text[..head_end].to_string(), links })
+}
+
+/// Whether a `/` at `index` starts a regular expression rather than a division
+fn starts_regex(bytes: &[u8], index: usize) -> bool {
+    let previous = bytes[..index].iter().rev().find(|byte| !byte.is_ascii_whitespace());
+    previous.is_none_or(|byte| b"(,[{:=!&|?;".contains(byte))
+}
+
+/// End of the comment, string, template or regular expression literal
+/// starting at `start`, if one starts there
+fn skip_token(bytes: &[u8], start: usize) -> Option<Option<usize>> {
+    match bytes[start] {
+        b'"' | b'\'' | b'`' => Some(skip_literal(bytes, start)),
+        b'/' if bytes.get(start + 1) == Some(&b'/') => {
+            let end = bytes[start..].iter().position(|&byte| byte == b'\n').map_or(bytes.len(), |at| start + at);
+            Some(Some(end))
+        }
+        b'/' if bytes.get(start + 1) == Some(&b'*') => {
+            let end = bytes[start + 2..].windows(2).position(|pair| pair == b"*/");
+            Some(end.map(|at| start + 2 + at + 2))
+        }
+        b'/' if starts_regex(bytes, start) => Some(skip_literal(bytes, start)),
+        _ => None,
+    }
+}
+
+/// End of the string, template or regular expression literal starting at `start`
+fn skip_literal(bytes: &[u8], start: usize) -> Option<usize> {
+    let quote = bytes[start];
+    let mut in_class = false;
+    let mut i = start + 1;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => i += 1,
+            b'\n' if quote != b'`' => return None,
+            b'[' if quote == b'/' => in_class = true,
+            b']' if quote == b'/' => in_class = false,
+            b'$' if quote == b'`' && bytes.get(i + 1) == Some(&b'{') => i = matching(bytes, i + 1)?,
+            byte if byte == quote && !in_class => {
+                i += 1;
+                if quote == b'/' {
+                    while i < bytes.len() && bytes[i].is_ascii_alphabetic() {
+                        i += 1;
+                    }
+                }
+                return Some(i);
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    None
+}
+
+/// Index of the bracket closing the one at `open`
+fn matching(bytes: &[u8], open: usize) -> Option<usize> {
+    let mut depth = 0;
+    let mut i = open;
+    while i < bytes.len() {
+        if let Some(end) = skip_token(bytes, i) {
+            i = end?;
+            continue;
+        }
+        match bytes[i] {
+            b'(' | b'[' | b'{' => depth += 1,
+            b')' | b']' | b'}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i);
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    None
+}
+
+/// Parts of `text` separated by `separator` outside brackets, comments and literals
+fn split_top(text: &str, separator: u8) -> Vec<&str> {
+    let bytes = text.as_bytes();
+    let mut parts = Vec::new();
+    let mut start = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        if let Some(end) = skip_token(bytes, i) {
+            i = end.unwrap_or(bytes.len());
+            continue;
+        }
+        match bytes[i] {
+            b'(' | b'[' | b'{' => i = matching(bytes, i).unwrap_or(bytes.len()),
+            byte if byte == separator => {
+                parts.push(&text[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    parts.push(&text[start..]);
+    parts.into_iter().map(str::trim).collect()
+}
+
+/// Operands of the binary `operator` in `text`, outside brackets and literals
+fn split_operator<'a>(text: &'a str, operator: &str) -> Vec<&'a str> {
+    let separator = format!(" {} ", operator);
+    let bytes = text.as_bytes();
+    let mut parts = Vec::new();
+    let mut start = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        if let Some(end) = skip_token(bytes, i) {
+            i = end.unwrap_or(bytes.len());
+            continue;
+        }
+        match bytes[i] {
+            b'(' | b'[' | b'{' => i = matching(bytes, i).unwrap_or(bytes.len()),
+            _ if text[i..].starts_with(&separator) => {
+                parts.push(&text[start..i]);
+                start = i + separator.len();
+                i = start;
+                continue;
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    parts.push(&text[start..]);
+    parts
+}
+
+/// Whether every bracket opened in `text` is closed in it
+fn is_balanced(text: &str) -> bool {
+    let bytes = text.as_bytes();
+    let mut i = 0;
+    while i < bytes.len() {
+        if let Some(end) = skip_token(bytes, i) {
+            match end {
+                Some(end) => i = end,
+                None => return false,
+            }
+            continue;
+        }
+        match bytes[i] {
+            b'(' | b'[' | b'{' => match matching(bytes, i) {
+                Some(close) => i = close + 1,
+                None => return false,
+            },
+            b')' | b']' | b'}' => return false,
+            _ => i += 1,
+        }
+    }
+    true
+}
+
+/// Brackets opened minus brackets closed on a line
+fn depth_change(line: &str) -> i32 {
+    if is_comment(line.trim_start()) {
+        return 0;
+    }
+    let bytes = line.as_bytes();
+    let mut depth = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        if let Some(end) = skip_token(bytes, i) {
+            i = end.unwrap_or(bytes.len());
+            continue;
+        }
+        match bytes[i] {
+            b'(' | b'[' | b'{' => depth += 1,
+            b')' | b']' | b'}' => depth -= 1,
+            _ => {}
+        }
+        i += 1;
+    }
+    depth
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_short_lines_are_unchanged() {
+        let content = concat!(
+            "import { z } from \"zod\";\n",
+            "\n",
+            "export const Role = z.enum([\"A\", \"B\"]);\n",
+            "export const User = z.object({\n",
+            "  zip: z.number().int().min(-2147483648).max(2147483647).optional(),\n",
+            "  role: Role.optional(),\n",
+            "});\n",
+        );
+        assert_eq!(layout(content), content);
+    }
+
+    #[test]
+    fn test_long_chains_get_one_call_per_line() {
+        assert_eq!(
+            layout("  age: z.number().int().min(0).max(120).describe(\"User's age in years\").optional(),"),
+            concat!(
+                "  age: z\n",
+                "    .number()\n",
+                "    .int()\n",
+                "    .min(0)\n",
+                "    .max(120)\n",
+                "    .describe(\"User's age in years\")\n",
+                "    .optional(),\n",
+            )
+        );
+        // Complex arguments break chains of more than two calls even when they fit
+        assert_eq!(
+            layout("  id: z.string().transform((value) => value as Id).optional(),"),
+            "  id: z\n    .string()\n    .transform((value) => value as Id)\n    .optional(),\n"
+        );
+        assert_eq!(
+            layout("export const UserType = z.enum([\"STANDARD\", \"ADMIN\", \"MODERATOR\"]).describe(\"Types of users\");"),
+            concat!(
+                "export const UserType = z\n",
+                "  .enum([\"STANDARD\", \"ADMIN\", \"MODERATOR\"])\n",
+                "  .describe(\"Types of users\");\n",
+            )
+        );
+    }
+
+    #[test]
+    fn test_expanded_objects_break_chains() {
+        let content = concat!(
+            "export const Event = z.object({\n",
+            "  start: z.string(),\n",
+            "}).superRefine((data, ctx) => {\n",
+            "  if (data.start === \"\") {\n",
+            "    ctx.addIssue({ code: z.ZodIssueCode.custom, path: [\"start\"], message: \"start must be set\" });\n",
+            "  }\n",
+            "});\n",
+        );
+        assert_eq!(
+            layout(content),
+            concat!(
+                "export const Event = z\n",
+                "  .object({\n",
+                "    start: z.string(),\n",
+                "  })\n",
+                "  .superRefine((data, ctx) => {\n",
+                "    if (data.start === \"\") {\n",
+                "      ctx.addIssue({\n",
+                "        code: z.ZodIssueCode.custom,\n",
+                "        path: [\"start\"],\n",
+                "        message: \"start must be set\",\n",
+                "      });\n",
+                "    }\n",
+                "  });\n",
+            )
+        );
+        // Arguments before an expanded one are broken out too
+        assert_eq!(
+            layout("export const A = z.preprocess(acceptProtoNames({ zip_code: \"zipCode\" }), z.object({\n  zipCode: z.string(),\n}));"),
+            concat!(
+                "export const A = z.preprocess(\n",
+                "  acceptProtoNames({ zip_code: \"zipCode\" }),\n",
+                "  z.object({\n",
+                "    zipCode: z.string(),\n",
+                "  }),\n",
+                ");\n",
+            )
+        );
+    }
+
+    #[test]
+    fn test_arguments_break_and_hug() {
+        assert_eq!(
+            layout("export const Status = z.enum([\"STATUS_UNKNOWN\", \"STATUS_ACTIVE\", \"STATUS_INACTIVE\", \"STATUS_DELETED\"]);"),
+            concat!(
+                "export const Status = z.enum([\n",
+                "  \"STATUS_UNKNOWN\",\n",
+                "  \"STATUS_ACTIVE\",\n",
+                "  \"STATUS_INACTIVE\",\n",
+                "  \"STATUS_DELETED\",\n",
+                "]);\n",
+            )
+        );
+        assert_eq!(
+            layout("    ctx.addIssue({ code: z.ZodIssueCode.custom, path: [\"email\"], message: \"At most one of email, phone may be set\" });"),
+            concat!(
+                "    ctx.addIssue({\n",
+                "      code: z.ZodIssueCode.custom,\n",
+                "      path: [\"email\"],\n",
+                "      message: \"At most one of email, phone may be set\",\n",
+                "    });\n",
+            )
+        );
+    }
+
+    #[test]
+    fn test_conditions_and_arrow_bodies() {
+        assert_eq!(
+            layout("  if (data.end_time != null && data.start_time != null && data.end_time <= data.start_time) {\n  }"),
+            concat!(
+                "  if (\n",
+                "    data.end_time != null &&\n",
+                "    data.start_time != null &&\n",
+                "    data.end_time <= data.start_time\n",
+                "  ) {\n",
+                "  }\n",
+            )
+        );
+        assert_eq!(
+            layout("    if ([data[\"email\"], data[\"phone\"]].filter((value) => value != null).length !== 1) {\n    }"),
+            concat!(
+                "    if (\n",
+                "      [data[\"email\"], data[\"phone\"]].filter((value) => value != null).length !==\n",
+                "      1\n",
+                "    ) {\n",
+                "    }\n",
+            )
+        );
+        assert_eq!(
+            layout("  total: z.string().refine((value) => BigInt(value) >= -9223372036854775808n && BigInt(value) <= 9223372036854775807n),"),
+            concat!(
+                "  total: z\n",
+                "    .string()\n",
+                "    .refine(\n",
+                "      (value) =>\n",
+                "        BigInt(value) >= -9223372036854775808n &&\n",
+                "        BigInt(value) <= 9223372036854775807n,\n",
+                "    ),\n",
+            )
+        );
+    }
+
+    #[test]
+    fn test_imports_and_type_aliases() {
+        assert_eq!(
+            layout("import { ConnectTransportOptions, connectUnary, connectServerStream } from \"./connect\";"),
+            "import {\n  ConnectTransportOptions,\n  connectUnary,\n  connectServerStream,\n} from \"./connect\";\n"
+        );
+        assert_eq!(
+            layout("export type Status = \"STATUS_UNKNOWN\" | \"STATUS_ACTIVE\" | \"STATUS_INACTIVE\" | \"STATUS_DELETED\";"),
+            concat!(
+                "export type Status =\n",
+                "  | \"STATUS_UNKNOWN\"\n",
+                "  | \"STATUS_ACTIVE\"\n",
+                "  | \"STATUS_INACTIVE\"\n",
+                "  | \"STATUS_DELETED\";\n",
+            )
+        );
+        assert_eq!(
+            layout("export type ComplexMessage_Address_Kind = z.infer<typeof ComplexMessage_Address_Kind>;"),
+            "export type ComplexMessage_Address_Kind = z.infer<\n  typeof ComplexMessage_Address_Kind\n>;\n"
+        );
+    }
+
+    #[test]
+    fn test_blank_lines_are_collapsed() {
+        assert_eq!(
+            layout("\n\nimport { z } from \"zod\";\n\n\n\nconst a = {\n\n  b: 1,\n\n};\n\n"),
+            "import { z } from \"zod\";\n\nconst a = {\n  b: 1,\n};\n"
+        );
+    }
+
+    #[test]
+    fn test_strings_take_prettier_quotes() {
+        assert_eq!(layout(r#"z.string().describe("Say \"hi\"");"#), "z.string().describe('Say \"hi\"');\n");
+        assert_eq!(layout(r#"z.string().describe("It's \"a\" 'b'");"#), "z.string().describe(\"It's \\\"a\\\" 'b'\");\n");
+        assert_eq!(layout(r#"z.string().describe('a\'b', "\n");"#), "z.string().describe(\"a'b\", \"\\n\");\n");
+        // Comments and regular expressions are left alone
+        assert_eq!(layout("// \"a\"\nz.string().regex(/\"/);"), "// \"a\"\nz.string().regex(/\"/);\n");
+    }
+}
@@ -1,6 +1,9 @@
 use std::collections::HashMap;
-use serde::{Deserialize, Serialize};
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer, Serialize};
+use log::warn;
 use serde_json::Value;
+use crate::zod::config::UnknownKeys;
 
 /// Represents Zod validation metadata extracted from Protocol Buffer comments
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -23,20 +26,97 @@ pub struct ZodMetadata {
     /// URL validation (for string fields)
     pub url: Option<bool>,
     
+    /// UUID validation (for string fields)
+    pub uuid: Option<bool>,
+    
     /// Regular expression pattern
     pub regex: Option<String>,
     
+    /// Brand distinguishing values of the same type, e.g. `UserId` for ID fields
+    pub brand: Option<String>,
+    
+    /// Name of a preset whose rules this metadata extends
+    pub preset: Option<String>,
+    
     /// Default value
     pub default: Option<Value>,
     
     /// Mark field as optional
     pub optional: Option<bool>,
     
+    /// Allow `null` as the field's value
+    pub nullable: Option<bool>,
+    
     /// Array validation
     pub array: Option<HashMap<String, Value>>,
     
-    /// Custom validations not covered by built-in options
+    /// Custom validations not covered by built-in options, keyed by validator name
+    ///
+    /// Accepts `"isValidSku"`, `["isValidSku", "inStock"]` or
+    /// `{ isValidSku: { message: "Unknown SKU" } }`; `false` disables a validator.
+    #[serde(default, deserialize_with = "deserialize_custom")]
     pub custom: Option<HashMap<String, Value>>,
+    
+    /// Cross-field rules checked against the whole message (messages only)
+    #[serde(default, deserialize_with = "deserialize_refinements")]
+    pub refinements: Option<Vec<CrossFieldRule>>,
+    
+    /// How the message object treats unknown keys (messages only)
+    pub unknown_keys: Option<UnknownKeys>,
+}
+
+/// A declarative rule relating several fields of a message
+///
+/// Written as `{ rule: "after", field: "end_time", other: "start_time" }`.
+/// Fields that are `undefined` or `null` are treated as not set; comparison
+/// rules only apply when both fields are set.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "rule", rename_all = "snake_case")]
+pub enum CrossFieldRule {
+    /// `field` must be later than `other` (timestamps, dates or numbers)
+    After {
+        field: String,
+        other: String,
+        #[serde(default)]
+        message: Option<String>,
+    },
+    
+    /// `field` must be earlier than `other` (timestamps, dates or numbers)
+    Before {
+        field: String,
+        other: String,
+        #[serde(default)]
+        message: Option<String>,
+    },
+    
+    /// `field` must be equal to `other`
+    Equals {
+        field: String,
+        other: String,
+        #[serde(default)]
+        message: Option<String>,
+    },
+    
+    /// Exactly one of `fields` must be set
+    ExactlyOne {
+        fields: Vec<String>,
+        #[serde(default)]
+        message: Option<String>,
+    },
+    
+    /// At least one of `fields` must be set
+    AtLeastOne {
+        fields: Vec<String>,
+        #[serde(default)]
+        message: Option<String>,
+    },
+    
+    /// At most one of `fields` may be set
+    AtMostOne {
+        fields: Vec<String>,
+        #[serde(default)]
+        message: Option<String>,
+    },
 }
 
 /// Metadata for an entire Protocol Buffer file
@@ -50,6 +130,28 @@ pub struct ZodFileMetadata {
     
     /// Metadata for enums, keyed by enum name
     pub enums: HashMap<String, ZodMetadata>,
+    
+    /// Presets defined in the file with `// @zod-preset: <name> { ... }`
+    pub presets: HashMap<String, ZodMetadata>,
+    
+    /// Leading comments of messages, enums, fields and enum values, keyed by
+    /// dotted path, e.g. `User`, `User.name` or `Status.ACTIVE`
+    pub comments: HashMap<String, String>,
+}
+
+impl ZodFileMetadata {
+    /// Expand the `preset` references of every message, field and enum
+    pub fn resolve_presets(&mut self, presets: &HashMap<String, ZodMetadata>) {
+        for message in self.messages.values_mut() {
+            message.message.resolve_preset(presets);
+            for field in message.fields.values_mut() {
+                field.resolve_preset(presets);
+            }
+        }
+        for enum_metadata in self.enums.values_mut() {
+            enum_metadata.resolve_preset(presets);
+        }
+    }
 }
 
 /// Metadata for a Protocol Buffer message
@@ -88,15 +190,27 @@ impl ZodMetadata {
         if let Some(v) = other.url {
             self.url = Some(v);
         }
+        if let Some(v) = other.uuid {
+            self.uuid = Some(v);
+        }
         if let Some(ref v) = other.regex {
             self.regex = Some(v.clone());
         }
+        if let Some(ref v) = other.brand {
+            self.brand = Some(v.clone());
+        }
+        if let Some(ref v) = other.preset {
+            self.preset = Some(v.clone());
+        }
         if let Some(ref v) = other.default {
             self.default = Some(v.clone());
         }
         if let Some(v) = other.optional {
             self.optional = Some(v);
         }
+        if let Some(v) = other.nullable {
+            self.nullable = Some(v);
+        }
         if let Some(ref v) = other.array {
             let mut new_array = v.clone();
             if let Some(ref mut existing) = self.array {
@@ -117,5 +231,120 @@ impl ZodMetadata {
                 self.custom = Some(new_custom);
             }
         }
+        if let Some(ref v) = other.refinements {
+            self.refinements = Some(v.clone());
+        }
+        if let Some(ref v) = other.unknown_keys {
+            self.unknown_keys = Some(v.clone());
+        }
     }
-}
\ No newline at end of file
+    
+    /// Expand the `preset` reference, if any
+    ///
+    /// The preset's rules form the base and this metadata is merged on top, so
+    /// local annotations override the preset. Presets may themselves extend
+    /// another preset.
+    pub fn resolve_preset(&mut self, presets: &HashMap<String, ZodMetadata>) {
+        let mut chain: Vec<&ZodMetadata> = Vec::new();
+        let mut seen: Vec<&str> = Vec::new();
+        let mut next = self.preset.as_deref();
+        
+        while let Some(name) = next {
+            if seen.contains(&name) {
+                warn!("Preset '{}' extends itself, ignoring the cycle", name);
+                break;
+            }
+            seen.push(name);
+            
+            match presets.get(name) {
+                Some(preset) => {
+                    chain.push(preset);
+                    next = preset.preset.as_deref();
+                }
+                None => {
+                    warn!("Unknown preset '{}'", name);
+                    break;
+                }
+            }
+        }
+        
+        if chain.is_empty() {
+            return;
+        }
+        
+        // Apply the most basic preset first
+        let mut resolved = ZodMetadata::new();
+        for preset in chain.into_iter().rev() {
+            resolved.merge(preset);
+        }
+        resolved.merge(self);
+        *self = resolved;
+    }
+}
+
+/// Deserialize the `custom` option from a validator name, a list of names or a map
+fn deserialize_custom<'de, D>(deserializer: D) -> Result<Option<HashMap<String, Value>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    match Option::<Value>::deserialize(deserializer)? {
+        None | Some(Value::Null) => Ok(None),
+        Some(Value::String(name)) => Ok(Some(HashMap::from([(name, Value::Bool(true))]))),
+        Some(Value::Array(names)) => names
+            .into_iter()
+            .map(|name| match name {
+                Value::String(name) => Ok((name, Value::Bool(true))),
+                other => Err(D::Error::custom(format!("invalid custom validator name: {}", other))),
+            })
+            .collect::<Result<_, _>>()
+            .map(Some),
+        Some(Value::Object(validators)) => Ok(Some(validators.into_iter().collect())),
+        Some(other) => Err(D::Error::custom(format!("invalid custom validator: {}", other))),
+    }
+}
+
+impl CrossFieldRule {
+    /// Names of the fields the rule refers to
+    pub fn fields(&self) -> Vec<&str> {
+        match self {
+            CrossFieldRule::After { field, other, .. }
+            | CrossFieldRule::Before { field, other, .. }
+            | CrossFieldRule::Equals { field, other, .. } => vec![field, other],
+            CrossFieldRule::ExactlyOne { fields, .. }
+            | CrossFieldRule::AtLeastOne { fields, .. }
+            | CrossFieldRule::AtMostOne { fields, .. } => fields.iter().map(String::as_str).collect(),
+        }
+    }
+    
+    /// Custom error message, if any
+    pub fn message(&self) -> Option<&str> {
+        match self {
+            CrossFieldRule::After { message, .. }
+            | CrossFieldRule::Before { message, .. }
+            | CrossFieldRule::Equals { message, .. }
+            | CrossFieldRule::ExactlyOne { message, .. }
+            | CrossFieldRule::AtLeastOne { message, .. }
+            | CrossFieldRule::AtMostOne { message, .. } => message.as_deref(),
+        }
+    }
+}
+
+/// Deserialize the `refinements` option from a single rule or a list of rules
+///
+/// Custom options produce a single object when a repeated rule is only set once.
+fn deserialize_refinements<'de, D>(deserializer: D) -> Result<Option<Vec<CrossFieldRule>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    match Option::<Value>::deserialize(deserializer)? {
+        None | Some(Value::Null) => Ok(None),
+        Some(Value::Array(rules)) => rules
+            .into_iter()
+            .map(|rule| serde_json::from_value(rule).map_err(D::Error::custom))
+            .collect::<Result<_, _>>()
+            .map(Some),
+        Some(rule) => serde_json::from_value(rule)
+            .map(|rule| Some(vec![rule]))
+            .map_err(D::Error::custom),
+    }
+}
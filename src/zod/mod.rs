@@ -1,13 +1,25 @@
 //! Module for Zod schema generation from Protocol Buffer definitions
 //!
 //! This module contains structures and functions for parsing Zod annotations
-//! from Protocol Buffer comments and generating Zod schemas.
+//! from Protocol Buffer comments and custom options, and generating Zod schemas.
 
 pub mod metadata;
 pub mod parser;
 pub mod generator;
 pub mod writer;
 pub mod config;
+pub mod options;
+pub mod validate;
+pub mod registry;
+pub mod dependencies;
+pub mod well_known;
+pub mod json_mapping;
+pub mod imports;
+pub mod connect;
+pub mod syntax;
+pub mod domain;
+pub mod jsdoc;
+pub mod layout;
 
 #[cfg(test)]
 mod tests;
@@ -15,4 +27,8 @@ mod tests;
 pub use metadata::ZodMetadata;
 pub use generator::{ZodGenerator, ZodGeneratorConfig, ImportStyle};
 pub use writer::TypeScriptWriter;
-pub use config::{Config, TargetLanguage, TsImportStyle};
\ No newline at end of file
+pub use config::{
+    Config, CustomValidatorConfig, EnumStyle, Int64Strategy, JsonMappingConfig, NestedTypeNaming,
+    OneofStyle, PresenceStrategy, RefinementKind, StructMapping, TargetLanguage, TimestampMapping,
+    TsImportStyle, TypeDeclarations, UnknownKeys, WellKnownTypesConfig, WrapperMapping, ZodVersion,
+};
\ No newline at end of file
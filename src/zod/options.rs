@@ -0,0 +1,273 @@
+//! Zod metadata from Protocol Buffer custom options
+//!
+//! Fields, messages and enums can carry typed validation rules through the
+//! extensions declared in `proto/zod/options.proto`:
+//!
+//! ```protobuf
+//! string username = 1 [(zod.field) = { min: 3, max: 50 }];
+//! string email = 2 [(zod.field).email = true];
+//! ```
+//!
+//! The rules are read into the same `ZodMetadata` used for `@zod` comments.
+
+use crate::parser::ast::{OptionValue, ProtoOption};
+use crate::zod::metadata::ZodMetadata;
+use log::warn;
+use serde_json::{Map, Number, Value};
+
+/// Option name of the field-level extension
+pub const FIELD_OPTION: &str = "(zod.field)";
+
+/// Option name of the message-level extension
+pub const MESSAGE_OPTION: &str = "(zod.message)";
+
+/// Option name of the enum-level extension
+pub const ENUM_OPTION: &str = "(zod.enum)";
+
+/// Read the Zod metadata carried by the given extension in a list of options
+///
+/// Both the aggregate form (`[(zod.field) = { min: 3 }]`) and the path form
+/// (`[(zod.field).min = 3]`) are supported; when an element uses both, later
+/// options override earlier ones. Returns `None` if the extension is not set.
+pub fn metadata_from_options(options: &[ProtoOption], extension: &str) -> Option<ZodMetadata> {
+    let mut rules = rules_from_options(options, extension)?;
+
+    // `default` is declared as a string in options.proto so that it can hold
+    // values of any type; decode it when it is a JSON literal
+    if let Some(Value::String(raw)) = rules.get("default") {
+        if let Ok(decoded) = serde_json::from_str::<Value>(raw) {
+            rules.insert("default".to_string(), decoded);
+        }
+    }
+
+    // A catchall schema is a separate option, as options.proto cannot hold
+    // the `{ catchall: ... }` form of `unknown_keys`
+    if let Some(schema) = rules.remove("catchall") {
+        let mut unknown_keys = Map::new();
+        unknown_keys.insert("catchall".to_string(), schema);
+        rules.insert("unknown_keys".to_string(), Value::Object(unknown_keys));
+    }
+
+    match serde_json::from_value::<ZodMetadata>(Value::Object(rules)) {
+        Ok(metadata) => Some(metadata),
+        Err(err) => {
+            warn!("Failed to read {} option: {}", extension, err);
+            None
+        }
+    }
+}
+
+/// The proto2 `[default = ...]` value of a field as JSON
+///
+/// String and `bytes` defaults are written with C escapes, which are decoded.
+pub fn default_from_options(options: &[ProtoOption]) -> Option<Value> {
+    let option = options.iter().find(|option| option.name == "default")?;
+    Some(match option.value {
+        OptionValue::String(ref escaped) => Value::String(unescape(escaped)),
+        ref value => option_value_to_json(value),
+    })
+}
+
+/// Decode the C escapes (`\n`, `\"`, `\x41`, `\101`) of a proto string literal
+fn unescape(escaped: &str) -> String {
+    let mut bytes = Vec::with_capacity(escaped.len());
+    let mut chars = escaped.chars().peekable();
+
+    while let Some(ch) = chars.next() {
+        if ch != '\\' {
+            let mut buffer = [0; 4];
+            bytes.extend_from_slice(ch.encode_utf8(&mut buffer).as_bytes());
+            continue;
+        }
+        let byte = match chars.next() {
+            Some('n') => b'\n',
+            Some('r') => b'\r',
+            Some('t') => b'\t',
+            Some('a') => 0x07,
+            Some('b') => 0x08,
+            Some('f') => 0x0c,
+            Some('v') => 0x0b,
+            Some(radix @ ('x' | 'X' | '0'..='7')) => {
+                let (base, max_digits, mut value) = match radix {
+                    'x' | 'X' => (16, 2, 0),
+                    digit => (8, 2, digit.to_digit(8).unwrap()),
+                };
+                for _ in 0..max_digits {
+                    match chars.peek().and_then(|digit| digit.to_digit(base)) {
+                        Some(digit) => {
+                            value = value * base + digit;
+                            chars.next();
+                        }
+                        None => break,
+                    }
+                }
+                value as u8
+            }
+            Some(other) => other as u8,
+            None => b'\\',
+        };
+        bytes.push(byte);
+    }
+
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+/// Collect the value of an extension option as a JSON object
+///
+/// Aggregate values and path-style options (`(ext).a.b = 1`) are combined
+/// into a single tree. Returns `None` if the extension is not set.
+pub fn rules_from_options(options: &[ProtoOption], extension: &str) -> Option<Map<String, Value>> {
+    let mut rules = Map::new();
+
+    for option in options {
+        if option.name == extension {
+            if let Value::Object(entries) = option_value_to_json(&option.value) {
+                merge_objects(&mut rules, entries);
+            }
+        } else if let Some(path) = option
+            .name
+            .strip_prefix(extension)
+            .and_then(|rest| rest.strip_prefix('.'))
+        {
+            insert_path(&mut rules, path, option_value_to_json(&option.value));
+        }
+    }
+
+    if rules.is_empty() {
+        None
+    } else {
+        Some(rules)
+    }
+}
+
+/// Convert an option value into JSON
+///
+/// Message literals become objects; a key that is repeated in text format
+/// (`in: "a" in: "b"`) becomes an array of its values.
+pub fn option_value_to_json(value: &OptionValue) -> Value {
+    match value {
+        OptionValue::Identifier(s) => Value::String(s.clone()),
+        OptionValue::String(s) => Value::String(s.clone()),
+        OptionValue::DecimalInt(i) | OptionValue::Octal(i) | OptionValue::Hex(i) => {
+            Value::Number((*i).into())
+        }
+        OptionValue::Float(f) => Number::from_f64(*f).map_or(Value::Null, Value::Number),
+        OptionValue::Bool(b) => Value::Bool(*b),
+        OptionValue::List(values) => Value::Array(values.iter().map(option_value_to_json).collect()),
+        OptionValue::Map(entries) => Value::Object(
+            entries
+                .iter()
+                .map(|(key, value)| (json_key(key), option_value_to_json(value)))
+                .collect(),
+        ),
+        OptionValue::Enum(_, value) => Value::String(value.clone()),
+        OptionValue::Message(entries) => {
+            let mut object = Map::new();
+            for (key, value) in entries {
+                let value = option_value_to_json(value);
+                match object.get_mut(key) {
+                    Some(Value::Array(existing)) if !matches!(value, Value::Array(_)) => {
+                        existing.push(value)
+                    }
+                    Some(existing) => {
+                        let previous = existing.take();
+                        *existing = Value::Array(vec![previous, value]);
+                    }
+                    None => {
+                        object.insert(key.clone(), value);
+                    }
+                }
+            }
+            Value::Object(object)
+        }
+    }
+}
+
+/// Render a map key as a JSON object key
+fn json_key(key: &OptionValue) -> String {
+    match option_value_to_json(key) {
+        Value::String(s) => s,
+        other => other.to_string(),
+    }
+}
+
+/// Insert a value at a dotted path (`array.min`), creating intermediate objects
+fn insert_path(object: &mut Map<String, Value>, path: &str, value: Value) {
+    match path.split_once('.') {
+        Some((head, rest)) => {
+            let entry = object
+                .entry(head.to_string())
+                .or_insert_with(|| Value::Object(Map::new()));
+            if !entry.is_object() {
+                *entry = Value::Object(Map::new());
+            }
+            if let Value::Object(nested) = entry {
+                insert_path(nested, rest, value);
+            }
+        }
+        // Setting the same path again adds an element to a repeated field
+        None => match object.get_mut(path) {
+            Some(Value::Array(existing)) if !value.is_array() => existing.push(value),
+            Some(existing) => {
+                let previous = existing.take();
+                *existing = Value::Array(vec![previous, value]);
+            }
+            None => {
+                object.insert(path.to_string(), value);
+            }
+        },
+    }
+}
+
+/// Recursively merge `source` into `target`, with `source` winning on conflicts
+fn merge_objects(target: &mut Map<String, Value>, source: Map<String, Value>) {
+    for (key, value) in source {
+        match (target.get_mut(&key), value) {
+            (Some(Value::Object(existing)), Value::Object(entries)) => {
+                merge_objects(existing, entries)
+            }
+            (_, value) => {
+                target.insert(key, value);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_metadata_from_aggregate_and_path_options() {
+        let options = vec![
+            ProtoOption::new(
+                FIELD_OPTION.to_string(),
+                OptionValue::Message(vec![
+                    ("min".to_string(), OptionValue::DecimalInt(3)),
+                    ("default".to_string(), OptionValue::String("42".to_string())),
+                ]),
+            ),
+            ProtoOption::new("(zod.field).email".to_string(), OptionValue::Bool(true)),
+            ProtoOption::new("(zod.field).array.max".to_string(), OptionValue::DecimalInt(10)),
+            ProtoOption::new("deprecated".to_string(), OptionValue::Bool(true)),
+        ];
+
+        let metadata = metadata_from_options(&options, FIELD_OPTION).unwrap();
+        assert_eq!(metadata.min, Some(3));
+        assert_eq!(metadata.email, Some(true));
+        assert_eq!(metadata.default, Some(Value::from(42)));
+        assert_eq!(metadata.array.unwrap().get("max"), Some(&Value::from(10)));
+
+        assert!(metadata_from_options(&options, MESSAGE_OPTION).is_none());
+    }
+
+    #[test]
+    fn test_default_from_options() {
+        let default = |value| default_from_options(&[ProtoOption::new("default".to_string(), value)]);
+        assert_eq!(default(OptionValue::String(r#"say \"hi\"\n\x41\101"#.to_string())),
+            Some(Value::from("say \"hi\"\nAA")));
+        assert_eq!(default(OptionValue::Identifier("MEMBER".to_string())), Some(Value::from("MEMBER")));
+        assert_eq!(default(OptionValue::DecimalInt(-5)), Some(Value::from(-5)));
+        assert!(default_from_options(&[]).is_none());
+    }
+}
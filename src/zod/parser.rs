@@ -1,16 +1,28 @@
 use crate::parser::ast::{Enum, Field, Message, ProtoFile};
+use std::collections::HashMap;
+use std::ops::Range;
 use crate::zod::metadata::{ZodFileMetadata, ZodMessageMetadata, ZodMetadata};
+use crate::zod::options::{self, ENUM_OPTION, FIELD_OPTION, MESSAGE_OPTION};
+use crate::zod::registry::qualified_name;
+use crate::zod::validate;
 use log::{debug, warn};
 use regex::Regex;
 use serde_json::from_str;
 use lazy_static::lazy_static;
+use std::fmt::Write;
 
-/// Parses Zod annotations from Protocol Buffer comments
+/// Parses Zod annotations from Protocol Buffer comments and custom options
+///
+/// Metadata is layered from lowest to highest precedence: proto2 `[default = ...]`
+/// values, protovalidate and protoc-gen-validate field rules, typed `(zod.field)` / `(zod.message)` /
+/// `(zod.enum)` options, and finally a `@zod { ... }` comment on the same element.
+/// Each layer wins for every rule it sets.
 pub struct ZodAnnotationParser;
 
 lazy_static! {
     static ref VERSION_RE: Regex = Regex::new(r"@zod-version:\s*([0-9.]+)").unwrap();
-    static ref ANNOTATION_RE: Regex = Regex::new(r"@zod\s*(\{.+?\})").unwrap();
+    static ref PRESET_RE: Regex = Regex::new(r"@zod-preset:\s*([\w-]+)\s*\{").unwrap();
+    static ref ANNOTATION_RE: Regex = Regex::new(r"@zod\s*\{").unwrap();
 }
 
 impl ZodAnnotationParser {
@@ -22,20 +34,240 @@ impl ZodAnnotationParser {
         Self::parse_file_level_comments(source, &mut file_metadata);
         
         // Parse message-level annotations
-        for message in &proto_file.messages {
-            let message_metadata = Self::parse_message(message, source);
-            file_metadata.messages.insert(message.name.clone(), message_metadata);
-        }
+        let file_body = 0..source.len();
+        Self::parse_messages(&proto_file.messages, "", &file_body, source, &mut file_metadata);
         
         // Parse enum-level annotations
         for enum_def in &proto_file.enums {
-            let enum_metadata = Self::parse_enum(enum_def, source);
+            let enum_metadata = Self::parse_enum(enum_def, &file_body, source);
             file_metadata.enums.insert(enum_def.name.clone(), enum_metadata);
         }
         
+        // Collect ordinary comments, which document the generated code
+        Self::parse_comments(&proto_file.messages, &proto_file.enums, "", &file_body, source,
+            &mut file_metadata.comments);
+        
         file_metadata
     }
     
+    /// Collect the leading comments of messages, enums and their members
+    ///
+    /// Definitions are looked up directly in `body`, the body of the enclosing
+    /// message or the whole file, so that members are found in their own
+    /// definition rather than in a nested one or a comment.
+    fn parse_comments(
+        messages: &[Message],
+        enums: &[Enum],
+        scope: &str,
+        body: &Range<usize>,
+        source: &str,
+        comments: &mut HashMap<String, String>,
+    ) {
+        for enum_def in enums {
+            let full_name = qualified_name(scope, &enum_def.name);
+            let pattern = format!(r"\benum\s+{}\b", enum_def.name);
+            let Some(enum_body) = Self::collect_comment(source, body, &pattern, &full_name, comments)
+                .and_then(|start| Self::definition_body(source, start)) else {
+                continue;
+            };
+            for value in &enum_def.values {
+                let pattern = format!(r"\b{}\s*=", value.name);
+                Self::collect_comment(source, &enum_body, &pattern, &qualified_name(&full_name, &value.name), comments);
+            }
+        }
+        
+        for message in messages {
+            let full_name = qualified_name(scope, &message.name);
+            let pattern = format!(r"\bmessage\s+{}\b", message.name);
+            let Some(message_body) = Self::collect_comment(source, body, &pattern, &full_name, comments)
+                .and_then(|start| Self::definition_body(source, start)) else {
+                continue;
+            };
+            
+            // Oneof members are defined in the body of their oneof
+            let mut fields: Vec<(&Field, Range<usize>)> = message.fields.iter()
+                .map(|field| (field, message_body.clone()))
+                .collect();
+            for oneof in &message.oneofs {
+                let oneof_body = Self::find_definition(source, &message_body, &format!(r"\boneof\s+{}\b", oneof.name))
+                    .and_then(|start| Self::definition_body(source, start))
+                    .unwrap_or(0..0);
+                fields.extend(oneof.fields.iter().map(|field| (field, oneof_body.clone())));
+            }
+            for (field, field_body) in fields {
+                let pattern = format!(r"\b{}\s*=\s*\d", field.name);
+                Self::collect_comment(source, &field_body, &pattern, &qualified_name(&full_name, &field.name), comments);
+            }
+            
+            Self::parse_comments(&message.nested_messages, &message.nested_enums, &full_name, &message_body,
+                source, comments);
+        }
+    }
+    
+    /// Record the leading comment of the definition matching `pattern` directly in `body`
+    ///
+    /// Returns the position of the definition, if found.
+    fn collect_comment(
+        source: &str,
+        body: &Range<usize>,
+        pattern: &str,
+        path: &str,
+        comments: &mut HashMap<String, String>,
+    ) -> Option<usize> {
+        let start = Self::find_definition(source, body, pattern)?;
+        if let Some(comment) = Self::leading_comment(source, start) {
+            comments.insert(path.to_string(), comment);
+        }
+        Some(start)
+    }
+    
+    /// The comment directly above the line containing `position`: consecutive
+    /// `//` lines or a `/* ... */` block, without `@zod` annotations
+    ///
+    /// Comments following code on their line belong to that code and end the
+    /// leading comment.
+    fn leading_comment(source: &str, position: usize) -> Option<String> {
+        let line_start = source[..position].rfind('\n').map_or(0, |i| i + 1);
+        let mut lines = Vec::new();
+        let mut in_block = false;
+        let mut block_start = 0;
+        
+        for line in source[..line_start].lines().rev().map(str::trim) {
+            let text = if in_block {
+                match line.find("/*") {
+                    Some(0) => {
+                        in_block = false;
+                        line.trim_start_matches("/*").trim_start_matches('*')
+                    }
+                    // The block is opened after code, as a trailing comment
+                    Some(_) => {
+                        lines.truncate(block_start);
+                        break;
+                    }
+                    None => line.trim_start_matches('*'),
+                }
+            } else if let Some(text) = line.strip_prefix("//") {
+                text
+            } else if let Some(text) = line.strip_suffix("*/") {
+                match text.find("/*") {
+                    Some(0) => text.trim_start_matches("/*").trim_start_matches('*'),
+                    Some(_) => break,
+                    None => {
+                        in_block = true;
+                        block_start = lines.len();
+                        text.trim_start_matches('*')
+                    }
+                }
+            } else {
+                break;
+            };
+            let text = text.strip_prefix(' ').unwrap_or(text).trim_end();
+            if !text.starts_with("@zod") {
+                lines.push(text);
+            }
+            if !in_block && line.starts_with("/*") {
+                break;
+            }
+        }
+        
+        // Lines were collected bottom-up; blank lines around the text are dropped
+        lines.reverse();
+        let comment = lines.join("\n");
+        let comment = comment.trim_matches('\n');
+        (!comment.is_empty()).then(|| comment.to_string())
+    }
+    
+    /// Parse annotations of messages and their nested types
+    ///
+    /// Messages are looked up in `body`, the body of their enclosing message or
+    /// the whole file. Nested messages and enums are keyed by their dotted path,
+    /// e.g. `ComplexMessage.Address`.
+    fn parse_messages(
+        messages: &[Message],
+        scope: &str,
+        body: &Range<usize>,
+        source: &str,
+        file_metadata: &mut ZodFileMetadata,
+    ) {
+        for message in messages {
+            let full_name = qualified_name(scope, &message.name);
+            let start = Self::find_definition(source, body, &format!(r"\bmessage\s+{}\b", message.name));
+            let message_body = start.and_then(|start| Self::definition_body(source, start)).unwrap_or(0..0);
+            
+            for enum_def in &message.nested_enums {
+                let enum_metadata = Self::parse_enum(enum_def, &message_body, source);
+                file_metadata.enums.insert(qualified_name(&full_name, &enum_def.name), enum_metadata);
+            }
+            Self::parse_messages(&message.nested_messages, &full_name, &message_body, source, file_metadata);
+            
+            let message_metadata = Self::parse_message(message, start, &message_body, source);
+            file_metadata.messages.insert(full_name, message_metadata);
+        }
+    }
+    
+    /// Start of the definition matching `pattern` directly in `body`, outside
+    /// comments, string literals and nested bodies
+    fn find_definition(source: &str, body: &Range<usize>, pattern: &str) -> Option<usize> {
+        let text = &source[body.clone()];
+        let depths = Self::code_depths(text);
+        Regex::new(pattern).unwrap()
+            .find_iter(text)
+            .map(|found| found.start())
+            .find(|&start| depths[start] == Some(0))
+            .map(|start| body.start + start)
+    }
+    
+    /// Span of the `{ ... }` body of the definition starting at `start`
+    fn definition_body(source: &str, start: usize) -> Option<Range<usize>> {
+        let text = &source[start..];
+        let depths = Self::code_depths(text);
+        let bytes = text.as_bytes();
+        let open = (0..bytes.len()).find(|&i| bytes[i] == b'{' && depths[i].is_some())?;
+        let close = (open + 1..bytes.len()).find(|&i| bytes[i] == b'}' && depths[i] == depths[open])?;
+        Some(start + open + 1..start + close)
+    }
+    
+    /// Brace depth at each position of `text` that is code rather than a
+    /// comment or string literal
+    ///
+    /// Braces have the depth outside the body they open or close.
+    fn code_depths(text: &str) -> Vec<Option<i32>> {
+        let bytes = text.as_bytes();
+        let mut depths = vec![None; bytes.len()];
+        let mut depth = 0;
+        let mut i = 0;
+        while i < bytes.len() {
+            let rest = &text[i..];
+            let skip = if rest.starts_with("//") {
+                rest.find('\n').unwrap_or(rest.len())
+            } else if let Some(comment) = rest.strip_prefix("/*") {
+                comment.find("*/").map_or(rest.len(), |end| end + 4)
+            } else if rest.starts_with(['"', '\'']) {
+                let quote = bytes[i];
+                let mut end = 1;
+                while end < rest.len() && bytes[i + end] != quote && bytes[i + end] != b'\n' {
+                    end += if bytes[i + end] == b'\\' { 2 } else { 1 };
+                }
+                (end + 1).min(rest.len())
+            } else {
+                match bytes[i] {
+                    b'{' => {
+                        depths[i] = Some(depth);
+                        depth += 1;
+                    }
+                    b'}' => {
+                        depth -= 1;
+                        depths[i] = Some(depth);
+                    }
+                    _ => depths[i] = Some(depth),
+                }
+                1
+            };
+            i += skip;
+        }
+        depths
+    }
+    
     /// Parse file-level comments for Zod annotations
     fn parse_file_level_comments(source: &str, file_metadata: &mut ZodFileMetadata) {
         // Extract version from comments like: syntax = "proto3"; // @zod-version: 1.0
@@ -45,86 +277,203 @@ impl ZodAnnotationParser {
                 debug!("Found Zod version: {}", version.as_str());
             }
         }
+        
+        // Extract presets from comments like: // @zod-preset: slug { regex: "^[a-z0-9-]+$" }
+        for captures in PRESET_RE.captures_iter(source) {
+            let (whole, name) = (captures.get(0).unwrap(), &captures[1]);
+            let annotation = Self::balanced_object(&source[whole.end() - 1..]);
+            if let Some(metadata) = annotation.and_then(Self::parse_json_metadata) {
+                debug!("Found Zod preset: {}", name);
+                file_metadata.presets.insert(name.to_string(), metadata);
+            }
+        }
     }
     
     /// Parse message-level and field-level annotations
-    fn parse_message(message: &Message, source: &str) -> ZodMessageMetadata {
+    ///
+    /// `start` is where the message is defined in the source, and the
+    /// annotations of its fields are looked up in its `body`.
+    fn parse_message(message: &Message, start: Option<usize>, body: &Range<usize>, source: &str) -> ZodMessageMetadata {
         let mut message_metadata = ZodMessageMetadata::default();
         
-        // Find message definition in source code to extract comment annotations
-        // This is a simplified approach - in practice you'd need to use source locations from the parser
-        let message_pattern = format!(r"message\s+{}\s*//\s*@zod\s*(\{{.*?\}})", message.name);
-        let message_re = Regex::new(&message_pattern).unwrap();
+        if let Some(metadata) = options::metadata_from_options(&message.options, MESSAGE_OPTION) {
+            message_metadata.message = metadata;
+        }
+        
+        // Comment annotations of the message follow its name
+        let start = start.unwrap_or(source.len());
+        let message_pattern = format!(r"^message\s+{}\s*//\s*@zod\s*\{{", message.name);
         
-        if let Some(captures) = message_re.captures(source) {
-            if let Some(annotation) = captures.get(1) {
-                if let Some(metadata) = Self::parse_json_metadata(annotation.as_str()) {
-                    message_metadata.message = metadata;
-                }
+        if let Some(annotation) = Self::find_annotation(&source[start..], &message_pattern) {
+            if let Some(metadata) = Self::parse_json_metadata(annotation) {
+                message_metadata.message.merge(&metadata);
             }
         }
         
-        // Parse field-level annotations
+        // Parse field-level annotations, including oneof members in the oneof's body
         for field in &message.fields {
-            let field_metadata = Self::parse_field(field, source);
+            let field_metadata = Self::parse_field(field, body, source);
             message_metadata.fields.insert(field.name.clone(), field_metadata);
         }
+        for oneof in &message.oneofs {
+            let oneof_body = Self::find_definition(source, body, &format!(r"\boneof\s+{}\b", oneof.name))
+                .and_then(|start| Self::definition_body(source, start))
+                .unwrap_or(0..0);
+            for field in &oneof.fields {
+                let field_metadata = Self::parse_field(field, &oneof_body, source);
+                message_metadata.fields.insert(field.name.clone(), field_metadata);
+            }
+        }
         
         message_metadata
     }
     
-    /// Parse enum-level annotations
-    fn parse_enum(enum_def: &Enum, source: &str) -> ZodMetadata {
-        let mut enum_metadata = ZodMetadata::new();
+    /// Parse enum-level annotations, of the enum defined in `body`
+    fn parse_enum(enum_def: &Enum, body: &Range<usize>, source: &str) -> ZodMetadata {
+        let mut enum_metadata = options::metadata_from_options(&enum_def.options, ENUM_OPTION)
+            .unwrap_or_default();
         
         // Find enum definition in source code to extract comment annotations
-        let enum_pattern = format!(r"enum\s+{}\s*//\s*@zod\s*(\{{.*?\}})", enum_def.name);
-        let enum_re = Regex::new(&enum_pattern).unwrap();
+        let start = Self::find_definition(source, body, &format!(r"\benum\s+{}\b", enum_def.name))
+            .unwrap_or(source.len());
+        let enum_pattern = format!(r"^enum\s+{}\s*//\s*@zod\s*\{{", enum_def.name);
         
-        if let Some(captures) = enum_re.captures(source) {
-            if let Some(annotation) = captures.get(1) {
-                if let Some(metadata) = Self::parse_json_metadata(annotation.as_str()) {
-                    enum_metadata = metadata;
-                }
+        if let Some(annotation) = Self::find_annotation(&source[start..], &enum_pattern) {
+            if let Some(metadata) = Self::parse_json_metadata(annotation) {
+                enum_metadata.merge(&metadata);
             }
         }
         
         enum_metadata
     }
     
-    /// Parse field-level annotations
-    fn parse_field(field: &Field, source: &str) -> ZodMetadata {
-        let mut field_metadata = ZodMetadata::new();
+    /// Parse field-level annotations, of the field defined in `body`
+    fn parse_field(field: &Field, body: &Range<usize>, source: &str) -> ZodMetadata {
+        // Existing protovalidate / protoc-gen-validate rules form the base layer
+        let mut field_metadata = validate::metadata_from_validation_rules(&field.options)
+            .unwrap_or_default();
+        field_metadata.default = options::default_from_options(&field.options);
+        if let Some(metadata) = options::metadata_from_options(&field.options, FIELD_OPTION) {
+            field_metadata.merge(&metadata);
+        }
         
-        // Find field definition in source code to extract comment annotations
-        let field_pattern = format!(r"{}\s*=\s*\d+.*?//\s*@zod\s*(\{{.*?\}})", 
-            field.name);
-        let field_re = Regex::new(&field_pattern).unwrap();
+        // Find field definition in its message to extract comment annotations
+        let start = Self::find_definition(source, body, &format!(r"\b{}\s*=\s*\d", field.name))
+            .unwrap_or(source.len());
+        let field_pattern = format!(r"^{}\s*=\s*\d+.*?//\s*@zod\s*\{{", field.name);
         
-        if let Some(captures) = field_re.captures(source) {
-            if let Some(annotation) = captures.get(1) {
-                if let Some(metadata) = Self::parse_json_metadata(annotation.as_str()) {
-                    field_metadata = metadata;
-                }
+        if let Some(annotation) = Self::find_annotation(&source[start..], &field_pattern) {
+            if let Some(metadata) = Self::parse_json_metadata(annotation) {
+                field_metadata.merge(&metadata);
             }
         }
         
         field_metadata
     }
     
+    /// Find the annotation object following the first match of `pattern`
+    ///
+    /// The pattern must end at the opening brace of the annotation. The object
+    /// extends to the matching closing brace, so annotations can contain nested
+    /// objects and arrays.
+    fn find_annotation<'a>(source: &'a str, pattern: &str) -> Option<&'a str> {
+        let re = Regex::new(pattern).unwrap();
+        let start = re.find(source)?.end() - 1;
+        Self::balanced_object(&source[start..])
+    }
+    
+    /// Return the `{ ... }` object at the start of `input`, up to its matching closing brace
+    fn balanced_object(input: &str) -> Option<&str> {
+        let mut depth = 0;
+        let mut in_string = false;
+        let mut escaped = false;
+        
+        for (i, ch) in input.char_indices() {
+            if in_string {
+                match ch {
+                    _ if escaped => escaped = false,
+                    '\\' => escaped = true,
+                    '"' => in_string = false,
+                    _ => {}
+                }
+                continue;
+            }
+            
+            match ch {
+                '"' => in_string = true,
+                '{' | '[' => depth += 1,
+                '}' | ']' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return Some(&input[..=i]);
+                    }
+                }
+                '\n' => return None,
+                _ => {}
+            }
+        }
+        
+        None
+    }
+    
+    /// Quote bare object keys and drop trailing commas so the annotation is valid JSON
+    fn normalize_json(json_str: &str) -> String {
+        let mut fixed = String::with_capacity(json_str.len());
+        let mut chars = json_str.chars().peekable();
+        
+        while let Some(ch) = chars.next() {
+            match ch {
+                // Copy string literals verbatim
+                '"' => {
+                    fixed.push(ch);
+                    let mut escaped = false;
+                    for ch in chars.by_ref() {
+                        fixed.push(ch);
+                        match ch {
+                            _ if escaped => escaped = false,
+                            '\\' => escaped = true,
+                            '"' => break,
+                            _ => {}
+                        }
+                    }
+                }
+                // Bare identifiers followed by a colon are keys
+                c if c.is_alphabetic() || c == '_' => {
+                    let mut word = c.to_string();
+                    while let Some(&c) = chars.peek() {
+                        if c.is_alphanumeric() || c == '_' {
+                            word.push(c);
+                            chars.next();
+                        } else {
+                            break;
+                        }
+                    }
+                    
+                    let rest: String = chars.clone().take_while(|c| c.is_whitespace()).collect();
+                    if chars.clone().nth(rest.chars().count()) == Some(':') {
+                        write!(fixed, "\"{}\"", word).unwrap();
+                    } else {
+                        fixed.push_str(&word);
+                    }
+                }
+                // Trailing commas before a closing brace or bracket
+                ',' => {
+                    let next = chars.clone().find(|c| !c.is_whitespace());
+                    if !matches!(next, Some('}') | Some(']')) {
+                        fixed.push(ch);
+                    }
+                }
+                _ => fixed.push(ch),
+            }
+        }
+        
+        fixed
+    }
+    
     /// Parse a JSON metadata string into a ZodMetadata struct
     fn parse_json_metadata(json_str: &str) -> Option<ZodMetadata> {
         // Add quotes around the keys to make it valid JSON
-        let mut json_with_quotes = String::new();
-        let re = Regex::new(r"(\w+)\s*:").unwrap();
-        let fixed_json = re.replace_all(json_str, "\"$1\":");
-        
-        // Fix trailing comma if present
-        let fixed_json = fixed_json.replace(",}", "}");
-        
-        // Fix any nested object key quotes
-        let re_nested = Regex::new(r"(\{)\s*(\w+)\s*:").unwrap();
-        let fixed_json = re_nested.replace_all(&fixed_json, "$1\"$2\":");
+        let fixed_json = Self::normalize_json(json_str);
         
         debug!("Original JSON: {}", json_str);
         debug!("Fixed JSON: {}", fixed_json);
@@ -140,9 +489,8 @@ impl ZodAnnotationParser {
     
     /// Extract all @zod annotations from a line of text
     pub fn extract_zod_annotations(line: &str) -> Option<String> {
-        ANNOTATION_RE.captures(line).map(|captures| {
-            captures.get(1).map_or("", |m| m.as_str()).to_string()
-        })
+        let start = ANNOTATION_RE.find(line)?.end() - 1;
+        Self::balanced_object(&line[start..]).map(str::to_string)
     }
 }
 
@@ -155,8 +503,101 @@ mod tests {
         let line = "string username = 1; // @zod { min: 3, max: 50 }";
         let annotation = ZodAnnotationParser::extract_zod_annotations(line);
         assert_eq!(annotation, Some("{ min: 3, max: 50 }".to_string()));
+        
+        let line = r#"message Event // @zod { refinements: [{ rule: "after", message: "a: {b}" }] } trailing"#;
+        let annotation = ZodAnnotationParser::extract_zod_annotations(line);
+        assert_eq!(
+            annotation,
+            Some(r#"{ refinements: [{ rule: "after", message: "a: {b}" }] }"#.to_string())
+        );
     }
     
+    #[test]
+    fn test_normalize_json() {
+        assert_eq!(
+            ZodAnnotationParser::normalize_json(r#"{ url: true, default: "http://x", "min": 1, array: { max: 2, }, }"#),
+            r#"{ "url": true, "default": "http://x", "min": 1, "array": { "max": 2 } }"#
+        );
+    }
+    
+    #[test]
+    fn test_leading_comments() {
+        let source = r#"
+// License header
+
+syntax = "proto3";
+
+// A user of the system
+// with a profile
+message User {
+    /* Display name */
+    string name = 1; // @zod { min: 1 }
+    /**
+     * Role of the user
+     */
+    Role role = 2;
+
+    string email = 3;
+}
+
+// @zod-preset: slug { min: 1 }
+enum Role {
+    // Full access
+    ADMIN = 0;
+}
+"#;
+        let proto_file = crate::parser::parse_proto_file(source).unwrap();
+        let comments = ZodAnnotationParser::parse_file(&proto_file, source).comments;
+        assert_eq!(comments["User"], "A user of the system\nwith a profile");
+        assert_eq!(comments["User.name"], "Display name");
+        assert_eq!(comments["User.role"], "Role of the user");
+        assert!(!comments.contains_key("User.email"));
+        assert!(!comments.contains_key("Role"));
+        assert_eq!(comments["Role.ADMIN"], "Full access");
+    }
+
+    #[test]
+    fn test_comments_of_own_definitions() {
+        let source = r#"
+/* License header */
+
+syntax = "proto3";
+
+// Unlike message Account, not stored
+
+// An account
+message Account {
+    string id = 1; /* primary key */
+    string name = 2;
+    string owner = 3; /* shown
+                         to admins */
+    string note = 4;
+
+    message Inner {
+        // inner email
+        string email = 1;
+    }
+
+    // outer email
+    string email = 5;
+    Inner inner = 6;
+
+    oneof contact {
+        // phone number
+        string phone = 7;
+    }
+}
+"#;
+        let proto_file = crate::parser::parse_proto_file(source).unwrap();
+        let comments = ZodAnnotationParser::parse_file(&proto_file, source).comments;
+        assert_eq!(comments["Account"], "An account");
+        assert!(!comments.contains_key("Account.name"));
+        assert!(!comments.contains_key("Account.note"));
+        assert_eq!(comments["Account.email"], "outer email");
+        assert_eq!(comments["Account.Inner.email"], "inner email");
+        assert_eq!(comments["Account.phone"], "phone number");
+    }
+
     #[test]
     fn test_extract_version() {
         let source = r#"syntax = "proto3"; // @zod-version: 1.0"#;
@@ -0,0 +1,171 @@
+//! Lookup of the messages and enums declared in a proto file
+//!
+//! Types are keyed by their name relative to the package, with nested types
+//! joined by dots (`ComplexMessage.Address`). References in fields are
+//! resolved with the protobuf scoping rules: the innermost enclosing message
+//! is searched first, then each outer scope in turn.
+
+use crate::parser::ast::{EnumValue, Message, ProtoFile};
+use std::collections::HashMap;
+
+/// Kind of a declared type
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TypeKind {
+    Message,
+    Enum,
+}
+
+/// The messages and enums declared in a proto file
+#[derive(Debug, Clone, Default)]
+pub struct TypeRegistry {
+    /// Package of the file, if any
+    package: Option<String>,
+
+    /// Declared types, keyed by their name relative to the package
+    types: HashMap<String, TypeKind>,
+
+    /// Values of the declared enums, keyed like `types`
+    enum_values: HashMap<String, Vec<EnumValue>>,
+}
+
+impl TypeRegistry {
+    /// Collect the types declared in a proto file, including nested ones
+    pub fn new(proto_file: &ProtoFile) -> Self {
+        let mut registry = TypeRegistry {
+            package: proto_file.package.clone(),
+            types: HashMap::new(),
+            enum_values: HashMap::new(),
+        };
+
+        for enum_def in &proto_file.enums {
+            registry.types.insert(enum_def.name.clone(), TypeKind::Enum);
+            registry.enum_values.insert(enum_def.name.clone(), enum_def.values.clone());
+        }
+        for message in &proto_file.messages {
+            registry.register_message(message, "");
+        }
+
+        registry
+    }
+
+    fn register_message(&mut self, message: &Message, scope: &str) {
+        let full_name = qualified_name(scope, &message.name);
+
+        for enum_def in &message.nested_enums {
+            let enum_name = qualified_name(&full_name, &enum_def.name);
+            self.types.insert(enum_name.clone(), TypeKind::Enum);
+            self.enum_values.insert(enum_name, enum_def.values.clone());
+        }
+        for nested in &message.nested_messages {
+            self.register_message(nested, &full_name);
+        }
+
+        self.types.insert(full_name, TypeKind::Message);
+    }
+
+    /// Package of the file, if any
+    pub fn package(&self) -> Option<&str> {
+        self.package.as_deref()
+    }
+
+    /// All declared types, by package-relative name
+    pub fn types(&self) -> impl Iterator<Item = (&str, TypeKind)> {
+        self.types.iter().map(|(name, kind)| (name.as_str(), *kind))
+    }
+
+    /// Kind of the type with the given package-relative name
+    pub fn kind(&self, full_name: &str) -> Option<TypeKind> {
+        self.types.get(full_name).copied()
+    }
+
+    /// Values of the enum with the given package-relative name
+    pub fn enum_values(&self, full_name: &str) -> Option<&[EnumValue]> {
+        self.enum_values.get(full_name).map(Vec::as_slice)
+    }
+
+    /// Resolve a type reference made inside `scope` to a package-relative name
+    ///
+    /// Returns `None` if the type is not declared in this file.
+    pub fn resolve(&self, scope: &str, name: &str) -> Option<String> {
+        // Fully qualified names are only looked up in this file's package
+        if let Some(absolute) = name.strip_prefix('.') {
+            let relative = self.strip_package(absolute)?;
+            return self.types.contains_key(relative).then(|| relative.to_string());
+        }
+
+        let mut scope = scope;
+        loop {
+            let candidate = qualified_name(scope, name);
+            if self.types.contains_key(&candidate) {
+                return Some(candidate);
+            }
+            if scope.is_empty() {
+                break;
+            }
+            scope = scope.rsplit_once('.').map_or("", |(outer, _)| outer);
+        }
+
+        // Names qualified with this file's package, e.g. `my.pkg.User`
+        let relative = self.strip_package(name)?;
+        self.types.contains_key(relative).then(|| relative.to_string())
+    }
+
+    /// Remove this file's package from a qualified name
+    fn strip_package<'a>(&self, name: &'a str) -> Option<&'a str> {
+        match self.package {
+            Some(ref package) => name
+                .strip_prefix(package.as_str())
+                .and_then(|rest| rest.strip_prefix('.')),
+            None => Some(name),
+        }
+    }
+}
+
+/// Join a scope and a name with a dot
+pub fn qualified_name(scope: &str, name: &str) -> String {
+    if scope.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}.{}", scope, name)
+    }
+}
+
+/// TypeScript identifier for a package-relative type name (`Outer.Inner` -> `Outer_Inner`)
+pub fn identifier(full_name: &str) -> String {
+    full_name.replace('.', "_")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_proto_file;
+
+    #[test]
+    fn test_resolve_scoped_names() {
+        let proto_file = parse_proto_file(
+            r#"
+            syntax = "proto3";
+            package shop.v1;
+
+            message Address { string street = 1; }
+
+            message Order {
+                message Address { string line = 1; }
+                message Item {
+                    enum Status { UNKNOWN = 0; }
+                }
+            }
+            "#,
+        )
+        .unwrap();
+        let registry = TypeRegistry::new(&proto_file);
+
+        assert_eq!(registry.resolve("Order.Item", "Address"), Some("Order.Address".to_string()));
+        assert_eq!(registry.resolve("", "Address"), Some("Address".to_string()));
+        assert_eq!(registry.resolve("Order", "Item.Status"), Some("Order.Item.Status".to_string()));
+        assert_eq!(registry.resolve("Order", ".shop.v1.Address"), Some("Address".to_string()));
+        assert_eq!(registry.resolve("", "shop.v1.Order.Item"), Some("Order.Item".to_string()));
+        assert_eq!(registry.resolve("Order", "google.protobuf.Timestamp"), None);
+        assert_eq!(registry.kind("Order.Item.Status"), Some(TypeKind::Enum));
+    }
+}
@@ -0,0 +1,253 @@
+//! Zod syntax of the supported Zod versions
+//!
+//! Zod 3 and Zod 4 chain methods on schemas (`z.string().min(3).optional()`),
+//! while `zod/mini` wraps schemas in functions and adds checks with `.check()`
+//! (`z.optional(z.string().check(z.minLength(3)))`). Zod 4 and `zod/mini` also
+//! have top-level string formats, strict and loose object constructors and
+//! partial records. The generator builds every schema through these helpers.
+
+use crate::zod::config::{UnknownKeys, ZodVersion};
+
+impl ZodVersion {
+    /// Module Zod is imported from
+    pub fn module(self) -> &'static str {
+        match self {
+            ZodVersion::V3 | ZodVersion::V4 => "zod",
+            ZodVersion::Mini => "zod/mini",
+        }
+    }
+
+    /// Wrap a schema in a modifier without arguments: `optional`, `nullable`, `nullish` or `array`
+    pub fn wrap(self, schema: &str, modifier: &str) -> String {
+        match self {
+            ZodVersion::V3 | ZodVersion::V4 => format!("{}.{}()", schema, modifier),
+            ZodVersion::Mini => format!("z.{}({})", modifier, schema),
+        }
+    }
+
+    /// Default used when the value is `undefined`
+    pub fn with_default(self, schema: &str, value: &str) -> String {
+        match self {
+            ZodVersion::V3 | ZodVersion::V4 => format!("{}.default({})", schema, value),
+            ZodVersion::Mini => format!("z._default({}, {})", schema, value),
+        }
+    }
+
+    /// Default input used when the value is `undefined`, parsed like any other input
+    ///
+    /// Zod 3 defaults are inputs, while Zod 4 defaults are outputs and skip parsing.
+    pub fn prefault(self, schema: &str, value: &str) -> String {
+        match self {
+            ZodVersion::V3 => format!("{}.default({})", schema, value),
+            ZodVersion::V4 => format!("{}.prefault({})", schema, value),
+            ZodVersion::Mini => format!("z.prefault({}, {})", schema, value),
+        }
+    }
+
+    /// Feed the output of a schema into another schema
+    pub fn pipe(self, schema: &str, target: &str) -> String {
+        match self {
+            ZodVersion::V3 | ZodVersion::V4 => format!("{}.pipe({})", schema, target),
+            ZodVersion::Mini => format!("z.pipe({}, {})", schema, target),
+        }
+    }
+
+    /// Transform the output of a schema with `function`
+    pub fn transform(self, schema: &str, function: &str) -> String {
+        match self {
+            ZodVersion::V3 | ZodVersion::V4 => format!("{}.transform({})", schema, function),
+            ZodVersion::Mini => format!("z.pipe({}, z.transform({}))", schema, function),
+        }
+    }
+
+    /// Intersection of two schemas
+    pub fn intersection(self, schema: &str, other: &str) -> String {
+        match self {
+            ZodVersion::V3 | ZodVersion::V4 => format!("{}.and({})", schema, other),
+            ZodVersion::Mini => format!("z.intersection({}, {})", schema, other),
+        }
+    }
+
+    /// Add a check: the `method` of chained syntax, or the `z.` function `mini_check` in `zod/mini`
+    pub fn check(self, schema: &str, method: &str, mini_check: &str, args: &str) -> String {
+        match self {
+            ZodVersion::V3 | ZodVersion::V4 => format!("{}.{}({})", schema, method, args),
+            ZodVersion::Mini => format!("{}.check(z.{}({}))", schema, mini_check, args),
+        }
+    }
+
+    /// Lower bound of a number, or minimum length of a string or array
+    pub fn min(self, schema: &str, min: &str, length: bool) -> String {
+        self.check(schema, "min", if length { "minLength" } else { "gte" }, min)
+    }
+
+    /// Upper bound of a number, or maximum length of a string or array
+    pub fn max(self, schema: &str, max: &str, length: bool) -> String {
+        self.check(schema, "max", if length { "maxLength" } else { "lte" }, max)
+    }
+
+    /// Exact length of a string or array
+    pub fn length(self, schema: &str, length: &str) -> String {
+        self.check(schema, "length", "length", length)
+    }
+
+    /// Regular expression a string must match
+    pub fn regex(self, schema: &str, pattern: &str) -> String {
+        self.check(schema, "regex", "regex", pattern)
+    }
+
+    /// Refinement with a predicate and optional parameters
+    pub fn refine(self, schema: &str, args: &str) -> String {
+        self.check(schema, "refine", "refine", args)
+    }
+
+    /// Refinement adding its own issues
+    pub fn super_refine(self, schema: &str, refinement: &str) -> String {
+        self.check(schema, "superRefine", "superRefine", refinement)
+    }
+
+    /// String format such as `email`, `url` or `uuid`
+    ///
+    /// Zod 4 and `zod/mini` have top-level formats, which replace a plain `z.string()`.
+    pub fn string_format(self, schema: &str, format: &str) -> String {
+        match self {
+            ZodVersion::V4 | ZodVersion::Mini if schema == "z.string()" => format!("z.{}()", format),
+            _ => self.check(schema, format, format, ""),
+        }
+    }
+
+    /// ISO 8601 date-time string with a UTC offset
+    pub fn datetime(self) -> &'static str {
+        match self {
+            ZodVersion::V3 => "z.string().datetime({ offset: true })",
+            ZodVersion::V4 | ZodVersion::Mini => "z.iso.datetime({ offset: true })",
+        }
+    }
+
+    /// Documentation of a schema, given as a JSON string literal
+    pub fn describe(self, schema: &str, description: &str) -> String {
+        match self {
+            ZodVersion::V3 => format!("{}.describe({})", schema, description),
+            ZodVersion::V4 => format!("{}.meta({{ description: {} }})", schema, description),
+            ZodVersion::Mini => format!("{}.register(z.globalRegistry, {{ description: {} }})", schema, description),
+        }
+    }
+
+    /// Brand distinguishing the output of a schema from other values of the same type
+    pub fn brand(self, schema: &str, brand: &str) -> String {
+        format!("{}.brand<{}>()", schema, brand)
+    }
+
+    /// TypeScript type of a branded value of `ts_type`, as output by [`ZodVersion::brand`]
+    pub fn branded_type(self, ts_type: &str, brand: &str) -> String {
+        match self {
+            ZodVersion::V3 => format!("{} & z.BRAND<{}>", ts_type, brand),
+            ZodVersion::V4 | ZodVersion::Mini => format!("{} & z.core.$brand<{}>", ts_type, brand),
+        }
+    }
+
+    /// Object schema with the given shape (`{ ... }`) and treatment of unknown keys
+    pub fn object(self, shape: &str, unknown_keys: &UnknownKeys) -> String {
+        match (unknown_keys, self) {
+            (UnknownKeys::Strip, _) => format!("z.object({})", shape),
+            (UnknownKeys::Strict, ZodVersion::V3) => format!("z.object({}).strict()", shape),
+            (UnknownKeys::Strict, _) => format!("z.strictObject({})", shape),
+            (UnknownKeys::Passthrough, ZodVersion::V3) => format!("z.object({}).passthrough()", shape),
+            (UnknownKeys::Passthrough, _) => format!("z.looseObject({})", shape),
+            (UnknownKeys::Catchall(schema), ZodVersion::Mini) => {
+                format!("z.catchall(z.object({}), {})", shape, schema)
+            }
+            (UnknownKeys::Catchall(schema), _) => format!("z.object({}).catchall({})", shape, schema),
+        }
+    }
+
+    /// Schema of a TypeScript enum or `as const` object
+    pub fn native_enum(self, values: &str) -> String {
+        match self {
+            ZodVersion::V3 => format!("z.nativeEnum({})", values),
+            ZodVersion::V4 | ZodVersion::Mini => format!("z.enum({})", values),
+        }
+    }
+
+    /// Schema of an integer number
+    pub fn integer(self) -> &'static str {
+        match self {
+            ZodVersion::V3 | ZodVersion::V4 => "z.number().int()",
+            ZodVersion::Mini => "z.int()",
+        }
+    }
+
+    /// Record schema; records with a finite set of keys (`partial`) may omit some of them
+    ///
+    /// Zod 4 records with enum keys are exhaustive, so those use `z.partialRecord`.
+    pub fn record(self, key: &str, value: &str, partial: bool) -> String {
+        match self {
+            ZodVersion::V4 | ZodVersion::Mini if partial => format!("z.partialRecord({}, {})", key, value),
+            _ => format!("z.record({}, {})", key, value),
+        }
+    }
+
+    /// Start of a schema preprocessing its input with `function`, closed by `)`
+    pub fn preprocess_start(self, function: &str) -> String {
+        match self {
+            ZodVersion::V3 | ZodVersion::V4 => format!("z.preprocess({}, ", function),
+            ZodVersion::Mini => format!("z.pipe(z.transform({}), ", function),
+        }
+    }
+
+    /// Type annotation of a schema whose output is `output`, for recursive schemas
+    ///
+    /// The input is the output type unless given, e.g. `unknown` when the input is preprocessed.
+    pub fn type_annotation(self, output: &str, input: Option<&str>) -> String {
+        match (self, input) {
+            (ZodVersion::V3, None) | (ZodVersion::V4, None) => format!("z.ZodType<{}>", output),
+            (ZodVersion::V3, Some(input)) => format!("z.ZodType<{}, z.ZodTypeDef, {}>", output, input),
+            (ZodVersion::V4, Some(input)) => format!("z.ZodType<{}, {}>", output, input),
+            (ZodVersion::Mini, None) => format!("z.ZodMiniType<{}>", output),
+            (ZodVersion::Mini, Some(input)) => format!("z.ZodMiniType<{}, {}>", output, input),
+        }
+    }
+
+    /// Type of any schema
+    pub fn schema_type(self) -> &'static str {
+        match self {
+            ZodVersion::V3 => "z.ZodTypeAny",
+            ZodVersion::V4 => "z.ZodType",
+            ZodVersion::Mini => "z.ZodMiniType",
+        }
+    }
+
+    /// Issue code of issues added by refinements
+    pub fn custom_issue_code(self) -> &'static str {
+        match self {
+            ZodVersion::V3 => "z.ZodIssueCode.custom",
+            ZodVersion::V4 | ZodVersion::Mini => "\"custom\"",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_schema_syntax() {
+        let schema = "z.string()";
+        assert_eq!(ZodVersion::V3.wrap(&ZodVersion::V3.min(schema, "3", true), "optional"),
+            "z.string().min(3).optional()");
+        assert_eq!(ZodVersion::Mini.wrap(&ZodVersion::Mini.min(schema, "3", true), "optional"),
+            "z.optional(z.string().check(z.minLength(3)))");
+        assert_eq!(ZodVersion::Mini.min("z.int()", "0", false), "z.int().check(z.gte(0))");
+
+        assert_eq!(ZodVersion::V3.string_format(schema, "email"), "z.string().email()");
+        assert_eq!(ZodVersion::V4.string_format(schema, "email"), "z.email()");
+        assert_eq!(ZodVersion::Mini.string_format("z.string().check(z.minLength(3))", "email"),
+            "z.string().check(z.minLength(3)).check(z.email())");
+
+        assert_eq!(ZodVersion::V4.object("{}", &UnknownKeys::Strict), "z.strictObject({})");
+        assert_eq!(ZodVersion::Mini.object("{}", &UnknownKeys::Catchall("z.string()".to_string())),
+            "z.catchall(z.object({}), z.string())");
+        assert_eq!(ZodVersion::V4.record("Color", "z.string()", true), "z.partialRecord(Color, z.string())");
+        assert_eq!(ZodVersion::V3.record("Color", "z.string()", true), "z.record(Color, z.string())");
+    }
+}
@@ -3,6 +3,21 @@ mod tests {
     use crate::parser::parse_proto_file;
     use crate::zod::parser::ZodAnnotationParser;
     use crate::zod::generator::{ZodGenerator, ZodGeneratorConfig, ImportStyle};
+    use crate::zod::config::{
+        CustomValidatorConfig, EnumStyle, Int64Strategy, JsonMappingConfig, NestedTypeNaming,
+        OneofStyle, PresenceStrategy, RefinementKind, StructMapping, TimestampMapping,
+        TypeDeclarations, UnknownKeys, WellKnownTypesConfig, ZodVersion,
+    };
+    use crate::zod::metadata::ZodMetadata;
+    use crate::zod::imports::{load_imports, source_path, ImportedFile};
+    use crate::zod::layout::layout;
+    use std::fs;
+    use std::path::{Path, PathBuf};
+    
+    /// Generated file without its provenance header
+    fn body(content: &str) -> &str {
+        content.split_once("\n\n").map_or(content, |(_, body)| body)
+    }
     
     #[test]
     fn test_extract_zod_annotations() {
@@ -96,6 +111,7 @@ mod tests {
             import_style: ImportStyle::Named,
             single_file: true,
             output_dir: "generated".to_string(),
+            ..ZodGeneratorConfig::default()
         };
         
         let generator = ZodGenerator::new(zod_metadata, config);
@@ -140,6 +156,7 @@ mod tests {
             import_style: ImportStyle::Named,
             single_file: true,
             output_dir: "generated".to_string(),
+            ..ZodGeneratorConfig::default()
         };
         
         let generator = ZodGenerator::new(zod_metadata, config);
@@ -150,11 +167,1721 @@ mod tests {
         // The generated code should contain User schema with validations
         let content = result.values().next().unwrap();
         assert!(content.contains("// Generated from Protocol Buffer version 1.0"));
-        assert!(content.contains("export const User = z.object({"));
+        assert!(content.contains("export const User = z\n  .object({"));
         assert!(content.contains("username: z.string().min(3).max(50).describe(\"User's name\")"));
         assert!(content.contains("email: z.string().email()"));
         assert!(content.contains("age: z.number().int().min(0).max(120)"));
         assert!(content.contains("tags: z.string().array()"));
-        assert!(content.contains("export const Role = z.enum(['USER', 'ADMIN', 'MODERATOR']).describe(\"User roles\")"));
+        assert!(content.contains(concat!(
+            "export const Role = z\n",
+            "  .enum([\"USER\", \"ADMIN\", \"MODERATOR\"])\n",
+            "  .describe(\"User roles\");\n",
+        )));
+    }
+    
+    #[test]
+    fn test_parse_custom_option_annotations() {
+        let proto_content = r#"
+            syntax = "proto3";
+            
+            import "zod/options.proto";
+            
+            message User {
+                option (zod.message) = { description: "User model" };
+                
+                string username = 1 [(zod.field) = { min: 3, max: 50 }];
+                string email = 2 [(zod.field).email = true]; // @zod { max: 100 }
+                int32 age = 3 [(zod.field) = { min: 0, max: 150 }]; // @zod { max: 120 }
+                string nickname = 4 [(zod.field).nullable = true];
+                repeated string tags = 5 [(zod.field).array = { length: 2 }];
+                string id = 6 [(zod.field).uuid = true];
+                
+                message Profile {
+                    int32 age = 1;
+                }
+            }
+            
+            message Admin {
+                option (zod.message).unknown_keys = "strict";
+                string email = 1;
+            }
+            
+            message Labels {
+                option (zod.message) = { unknown_keys: "strict" catchall: "z.string()" };
+                string name = 1;
+            }
+            
+            enum Role {
+                option (zod.enum) = { description: "User roles" };
+                USER = 0;
+            }
+        "#;
+        
+        let proto_file = parse_proto_file(proto_content).unwrap();
+        let zod_metadata = ZodAnnotationParser::parse_file(&proto_file, proto_content);
+        
+        let user_metadata = &zod_metadata.messages["User"];
+        assert_eq!(user_metadata.message.description, Some("User model".to_string()));
+        
+        let username_metadata = &user_metadata.fields["username"];
+        assert_eq!(username_metadata.min, Some(3));
+        assert_eq!(username_metadata.max, Some(50));
+        
+        // Options and comments are combined
+        let email_metadata = &user_metadata.fields["email"];
+        assert_eq!(email_metadata.email, Some(true));
+        assert_eq!(email_metadata.max, Some(100));
+        
+        // The comment wins when both set the same rule
+        let age_metadata = &user_metadata.fields["age"];
+        assert_eq!(age_metadata.min, Some(0));
+        assert_eq!(age_metadata.max, Some(120));
+        
+        assert_eq!(zod_metadata.enums["Role"].description, Some("User roles".to_string()));
+        
+        // Comments only annotate the field of their own message
+        assert_eq!(zod_metadata.messages["Admin"].fields["email"].max, None);
+        assert_eq!(zod_metadata.messages["User.Profile"].fields["age"].max, None);
+        
+        assert_eq!(zod_metadata.messages["Admin"].message.unknown_keys, Some(UnknownKeys::Strict));
+        assert_eq!(
+            zod_metadata.messages["Labels"].message.unknown_keys,
+            Some(UnknownKeys::Catchall("z.string()".to_string()))
+        );
+        
+        let generator = ZodGenerator::new(zod_metadata, ZodGeneratorConfig::default());
+        let content = generator.generate(&proto_file).into_values().next().unwrap();
+        assert!(content.contains("  nickname: z.string().nullable().optional(),"));
+        assert!(content.contains("  tags: z.string().array().length(2),"));
+        assert!(content.contains("  id: z.string().uuid().optional(),"));
+        assert!(content.contains("    email: z.string().optional(),\n  })\n  .strict();"));
+        assert!(content.contains("    name: z.string().optional(),\n  })\n  .catchall(z.string());"));
+    }
+    
+    #[test]
+    fn test_validation_rules_to_zod() {
+        let proto_content = r#"
+            syntax = "proto3";
+            
+            message CreateUserRequest {
+                string name = 1 [(buf.validate.field).string = { min_len: 1, max_len: 64 }];
+                string id = 2 [(buf.validate.field).string.uuid = true];
+                int32 age = 3 [(buf.validate.field).int32 = { gt: 0, lte: 150 }];
+                repeated string tags = 4 [(buf.validate.field).repeated = { max_items: 5, items { string { min_len: 2 } } }];
+                string email = 5 [(validate.rules).string.email = true, (buf.validate.field).required = true];
+                string nickname = 6 [(validate.rules).string.max_len = 20]; // @zod { max: 30 }
+            }
+        "#;
+        
+        let proto_file = parse_proto_file(proto_content).unwrap();
+        let zod_metadata = ZodAnnotationParser::parse_file(&proto_file, proto_content);
+        
+        let generator = ZodGenerator::new(zod_metadata, ZodGeneratorConfig::default());
+        let result = generator.generate(&proto_file);
+        let content = result.values().next().unwrap();
+        
+        assert!(content.contains("name: z.string().min(1).max(64).optional()"));
+        assert!(content.contains("id: z.string().uuid().optional()"));
+        assert!(content.contains("age: z.number().int().min(1).max(150).optional()"));
+        assert!(content.contains("tags: z.string().min(2).array().max(5)"));
+        assert!(content.contains("email: z.string().email(),"));
+        // `@zod` comments take precedence over validation rules
+        assert!(content.contains("nickname: z.string().max(30).optional()"));
+    }
+    
+    #[test]
+    fn test_custom_validators() {
+        let proto_content = r#"
+            syntax = "proto3";
+            
+            message Product {
+                string sku = 1; // @zod { custom: "isValidSku" }
+                string slug = 2 [(zod.field) = { custom: "isSlug" custom: "isLowercase" }];
+                string code = 3; // @zod { custom: "unknownValidator" }
+            }
+        "#;
+        
+        let proto_file = parse_proto_file(proto_content).unwrap();
+        let zod_metadata = ZodAnnotationParser::parse_file(&proto_file, proto_content);
+        
+        let mut config = ZodGeneratorConfig::default();
+        for (name, import, refinement, message) in [
+            ("isValidSku", "./validators", RefinementKind::Refine, Some("Invalid SKU")),
+            ("isSlug", "./validators", RefinementKind::Refine, None),
+            ("isLowercase", "@acme/checks", RefinementKind::SuperRefine, None),
+        ] {
+            config.custom_validators.insert(name.to_string(), CustomValidatorConfig {
+                import: import.to_string(),
+                refinement,
+                message: message.map(str::to_string),
+            });
+        }
+        
+        let generator = ZodGenerator::new(zod_metadata, config);
+        let result = generator.generate(&proto_file);
+        let content = result.values().next().unwrap();
+        
+        assert!(content.contains("import { isLowercase } from \"@acme/checks\";"));
+        assert!(content.contains("import { isSlug, isValidSku } from \"./validators\";"));
+        assert!(content.contains("sku: z.string().refine(isValidSku, { message: \"Invalid SKU\" }).optional()"));
+        assert!(content.contains("slug: z.string().superRefine(isLowercase).refine(isSlug).optional()"));
+        // Validators without a configured import are skipped
+        assert!(content.contains("code: z.string().optional()"));
+        assert!(!content.contains("unknownValidator"));
+    }
+    
+    #[test]
+    fn test_cross_field_refinements() {
+        let proto_content = r#"
+            syntax = "proto3";
+            
+            import "google/protobuf/timestamp.proto";
+            
+            message Event // @zod { refinements: [{ rule: "after", field: "end_time", other: "start_time" }] }
+            {
+                google.protobuf.Timestamp start_time = 1;
+                google.protobuf.Timestamp end_time = 2;
+            }
+            
+            message Range // @zod { refinements: [{ rule: "before", field: "low", other: "high" }, { rule: "before", field: "first", other: "last" }] }
+            {
+                int64 low = 1;
+                int64 high = 2;
+                int32 first = 3 [json_name = "first-index"];
+                int32 last = 4 [json_name = "last-index"];
+            }
+            
+            message SignUp {
+                option (zod.message).refinements = { rule: "equals" field: "confirm_password" other: "password" message: "Passwords do not match" };
+                option (zod.message).refinements = { rule: "exactly_one" fields: "email" fields: "phone" };
+                
+                string email = 1;
+                string phone = 2;
+                string password = 3;
+                string confirm_password = 4;
+            }
+        "#;
+        
+        let proto_file = parse_proto_file(proto_content).unwrap();
+        let zod_metadata = ZodAnnotationParser::parse_file(&proto_file, proto_content);
+        
+        let generator = ZodGenerator::new(zod_metadata.clone(), ZodGeneratorConfig::default());
+        let result = generator.generate(&proto_file);
+        let content = result.values().next().unwrap();
+        
+        assert!(content.contains(concat!(
+            "  })\n",
+            "  .superRefine((data, ctx) => {\n",
+            "    if (\n",
+            "      data[\"end_time\"] != null &&\n",
+            "      data[\"start_time\"] != null &&\n",
+            "      !(new Date(data[\"end_time\"]) > new Date(data[\"start_time\"]))\n",
+            "    ) {\n",
+            "      ctx.addIssue({\n",
+            "        code: z.ZodIssueCode.custom,\n",
+            "        path: [\"end_time\"],\n",
+            "        message: \"end_time must be after start_time\",\n",
+            "      });\n",
+            "    }\n",
+            "  });\n",
+        )));
+        assert!(content.contains("    if (data[\"confirm_password\"] !== data[\"password\"]) {\n      ctx.addIssue({\n"));
+        assert!(content.contains("      [data[\"email\"], data[\"phone\"]].filter((value) => value != null).length !==\n      1\n"));
+        assert!(content.contains("        path: [\"email\"],\n        message: \"Exactly one of email, phone must be set\",\n"));
+        assert!(content.contains("        path: [\"phone\"],\n        message: \"Exactly one of email, phone must be set\",\n"));
+        
+        // Only timestamps are compared as dates, and 64-bit integers in strings as bigints
+        assert!(content.contains("      !(BigInt(data[\"low\"]) < BigInt(data[\"high\"]))\n"));
+        assert!(content.contains("      !(data[\"first\"] < data[\"last\"])\n"));
+        
+        // JSON names need not be identifiers
+        let config = ZodGeneratorConfig {
+            json_mapping: JsonMappingConfig { enabled: true, ..JsonMappingConfig::default() },
+            ..ZodGeneratorConfig::default()
+        };
+        let content = ZodGenerator::new(zod_metadata, config).generate(&proto_file).into_values().next().unwrap();
+        assert!(content.contains("      !(data[\"first-index\"] < data[\"last-index\"])\n"));
+    }
+    
+    #[test]
+    fn test_annotation_presets() {
+        let proto_content = r#"
+            syntax = "proto3";
+            // @zod-preset: slug { regex: "^[a-z0-9-]+$", min: 1, max: 64 }
+            // @zod-preset: short_slug { preset: "slug", max: 16 }
+            
+            message Product {
+                string slug = 1; // @zod { preset: "slug" }
+                string handle = 2; // @zod { preset: "short_slug", min: 3 }
+                string sku = 3 [(zod.field).preset = "sku"];
+                string phone = 4; // @zod { preset: "phone" }
+            }
+        "#;
+        
+        let proto_file = parse_proto_file(proto_content).unwrap();
+        let zod_metadata = ZodAnnotationParser::parse_file(&proto_file, proto_content);
+        
+        let mut config = ZodGeneratorConfig::default();
+        config.presets.insert("sku".to_string(), ZodMetadata {
+            regex: Some("^[A-Z]{3}-\\d{4}$".to_string()),
+            ..ZodMetadata::default()
+        });
+        // File-level presets override configured ones
+        config.presets.insert("slug".to_string(), ZodMetadata {
+            max: Some(255),
+            ..ZodMetadata::default()
+        });
+        
+        let generator = ZodGenerator::new(zod_metadata, config);
+        let result = generator.generate(&proto_file);
+        let content = result.values().next().unwrap();
+        
+        assert!(content.contains("slug: z.string().min(1).max(64).regex(new RegExp(\"^[a-z0-9-]+$\")).optional()"));
+        assert!(content.contains(concat!(
+            "  handle: z\n",
+            "    .string()\n",
+            "    .min(3)\n",
+            "    .max(16)\n",
+            "    .regex(new RegExp(\"^[a-z0-9-]+$\"))\n",
+            "    .optional(),\n",
+        )));
+        assert!(content.contains("sku: z.string().regex(new RegExp(\"^[A-Z]{3}-\\\\d{4}$\")).optional()"));
+        // Unknown presets are ignored
+        assert!(content.contains("phone: z.string().optional()"));
+    }
+    
+    #[test]
+    fn test_nested_types() {
+        let proto_content = r#"
+            syntax = "proto3";
+            package shop;
+            
+            message ComplexMessage {
+                message Address {
+                    enum Kind { HOME = 0; WORK = 1; }
+                    string street = 1;
+                    Kind kind = 2;
+                }
+                enum Status { ACTIVE = 0; }
+                
+                Address address = 1;
+                repeated Address previous = 2;
+                Status status = 3;
+            }
+            
+            message Order {
+                ComplexMessage.Address shipping = 1;
+                .shop.ComplexMessage.Status status = 2;
+            }
+        "#;
+        
+        let proto_file = parse_proto_file(proto_content).unwrap();
+        
+        // Underscore naming
+        let zod_metadata = ZodAnnotationParser::parse_file(&proto_file, proto_content);
+        let generator = ZodGenerator::new(zod_metadata, ZodGeneratorConfig::default());
+        let result = generator.generate(&proto_file);
+        let content = result.values().next().unwrap();
+        
+        assert!(content.contains("export const ComplexMessage_Address_Kind = z.enum([\"HOME\", \"WORK\"]);"));
+        assert!(content.contains("export const ComplexMessage_Status = z.enum([\"ACTIVE\"]);"));
+        assert!(content.contains("export type ComplexMessage_Address = z.infer<typeof ComplexMessage_Address>;"));
+        assert!(content.contains("  kind: ComplexMessage_Address_Kind.optional(),"));
+        assert!(content.contains("  address: ComplexMessage_Address.optional(),"));
+        assert!(content.contains("  previous: ComplexMessage_Address.array(),"));
+        assert!(content.contains("  shipping: ComplexMessage_Address.optional(),"));
+        assert!(content.contains("  status: ComplexMessage_Status.optional(),"));
+        // Nested types are declared before the message using them
+        assert!(content.find("const ComplexMessage_Address =").unwrap()
+            < content.find("const ComplexMessage =").unwrap());
+        
+        // Namespaced naming
+        let zod_metadata = ZodAnnotationParser::parse_file(&proto_file, proto_content);
+        let config = ZodGeneratorConfig {
+            nested_naming: NestedTypeNaming::Namespace,
+            ..ZodGeneratorConfig::default()
+        };
+        let generator = ZodGenerator::new(zod_metadata, config);
+        let result = generator.generate(&proto_file);
+        let content = result.values().next().unwrap();
+        
+        assert!(content.contains("\nexport const ComplexMessage_Address = Object.assign(\n  z.object({\n"));
+        assert!(content.contains("  }),\n  {\n    Kind: ComplexMessage_Address_Kind,\n  },\n);"));
+        assert!(content.contains("export const ComplexMessage = Object.assign(\n  z.object({\n"));
+        assert!(content.contains(concat!(
+            "  }),\n",
+            "  {\n",
+            "    Status: ComplexMessage_Status,\n",
+            "    Address: ComplexMessage_Address,\n",
+            "  },\n",
+            ");\n",
+        )));
+        assert!(content.contains(concat!(
+            "export namespace ComplexMessage {\n",
+            "  export type Status = ComplexMessage_Status;\n",
+            "  export type Address = ComplexMessage_Address;\n",
+            "  export namespace Address {\n",
+            "    export type Kind = ComplexMessage_Address_Kind;\n",
+            "  }\n",
+            "}\n",
+        )));
+        // The declarations the namespace refers to are exported for declaration files
+        assert!(content.contains("export const ComplexMessage_Status = z.enum("));
+        assert!(content.contains("export type ComplexMessage_Address = z.infer<typeof ComplexMessage_Address>;"));
+    }
+    
+    #[test]
+    fn test_oneof_styles() {
+        let proto_content = r#"
+            syntax = "proto3";
+            
+            message Contact {
+                string name = 1;
+                oneof contact_info {
+                    string email = 2; // @zod { email: true }
+                    string phone = 3;
+                }
+            }
+        "#;
+        
+        let proto_file = parse_proto_file(proto_content).unwrap();
+        let generate = |oneof_style| {
+            let zod_metadata = ZodAnnotationParser::parse_file(&proto_file, proto_content);
+            let config = ZodGeneratorConfig { oneof_style, ..ZodGeneratorConfig::default() };
+            let generator = ZodGenerator::new(zod_metadata, config);
+            generator.generate(&proto_file).into_values().next().unwrap()
+        };
+        
+        let content = generate(OneofStyle::OptionalKeys);
+        assert!(content.contains("    email: z.string().email().optional(),\n    phone: z.string().optional(),\n  })"));
+        assert!(content.contains("    if (\n      [data[\"email\"], data[\"phone\"]].filter((value) => value != null).length > 1\n    ) {"));
+        assert!(content.contains("        path: [\"phone\"],\n        message: \"At most one of email, phone may be set\",\n"));
+        
+        let content = generate(OneofStyle::Union);
+        assert!(content.contains(concat!(
+            "    name: z.string().optional(),\n",
+            "  })\n",
+            "  .and(\n",
+            "    z.union([\n",
+            "      z.object({ email: z.string().email(), phone: z.undefined().optional() }),\n",
+            "      z.object({ email: z.undefined().optional(), phone: z.string() }),\n",
+            "      z.object({\n",
+            "        email: z.undefined().optional(),\n",
+            "        phone: z.undefined().optional(),\n",
+            "      }),\n",
+            "    ]),\n",
+            "  );\n",
+        )));
+        
+        let content = generate(OneofStyle::DiscriminatedUnion);
+        assert!(content.contains(concat!(
+            "  contact_info: z.discriminatedUnion(\"case\", [\n",
+            "    z.object({ case: z.literal(\"email\"), value: z.string().email() }),\n",
+            "    z.object({ case: z.literal(\"phone\"), value: z.string() }),\n",
+            "    z.object({ case: z.undefined(), value: z.undefined().optional() }),\n",
+            "  ]),\n",
+            "});\n",
+        )));
+    }
+    
+    #[test]
+    fn test_dependency_order_and_recursion() {
+        let proto_content = r#"
+            syntax = "proto3";
+            
+            message User {
+                Address address = 1;
+            }
+            
+            message Address {
+                string street = 1;
+            }
+            
+            message TreeNode {
+                string label = 1;
+                repeated TreeNode children = 2;
+                map<string, TreeNode> named = 3;
+            }
+            
+            message Thread {
+                repeated Comment comments = 1;
+            }
+            
+            message Comment {
+                string text = 1;
+                Thread replies = 2;
+            }
+        "#;
+        
+        let proto_file = parse_proto_file(proto_content).unwrap();
+        let zod_metadata = ZodAnnotationParser::parse_file(&proto_file, proto_content);
+        let generator = ZodGenerator::new(zod_metadata, ZodGeneratorConfig::default());
+        let result = generator.generate(&proto_file);
+        let content = result.values().next().unwrap();
+        
+        // Referenced messages are declared first
+        assert!(content.find("export const Address =").unwrap()
+            < content.find("export const User =").unwrap());
+        assert!(content.contains("  address: Address.optional(),"));
+        
+        // Self-referencing messages
+        assert!(content.contains(concat!(
+            "export type TreeNode = {\n",
+            "  label?: string;\n",
+            "  children: TreeNode[];\n",
+            "  named?: Record<string, TreeNode>;\n",
+            "};\n",
+            "export const TreeNode: z.ZodType<TreeNode, z.ZodTypeDef, unknown> = z.object({\n",
+            "  label: z.string().optional(),\n",
+            "  children: z.lazy(() => TreeNode).array(),\n",
+            "  named: z.record(z.string(), z.lazy(() => TreeNode)).optional(),\n",
+            "});\n",
+        )));
+        assert!(!content.contains("export type TreeNode = z.infer"));
+        
+        // Mutually recursive messages
+        assert!(content.contains(concat!(
+            "export const Thread: z.ZodType<Thread, z.ZodTypeDef, unknown> = z.object({\n",
+            "  comments: z.lazy(() => Comment).array(),",
+        )));
+        assert!(content.contains("export const Comment: z.ZodType<Comment, z.ZodTypeDef, unknown> = z.object({"));
+        assert!(content.contains("  replies: z.lazy(() => Thread).optional(),"));
+        
+        // Inputs are left open, since they can differ from the output
+        let zod_metadata = ZodAnnotationParser::parse_file(&proto_file, proto_content);
+        let config = ZodGeneratorConfig { zod_version: ZodVersion::V4, ..ZodGeneratorConfig::default() };
+        let content = ZodGenerator::new(zod_metadata, config).generate(&proto_file).into_values().next().unwrap();
+        assert!(content.contains("export const TreeNode: z.ZodType<TreeNode, unknown> = z.object({\n"));
+    }
+    
+    #[test]
+    fn test_well_known_types() {
+        let proto_content = r#"
+            syntax = "proto3";
+            
+            import "google/protobuf/timestamp.proto";
+            import "google/protobuf/duration.proto";
+            import "google/protobuf/struct.proto";
+            import "google/protobuf/wrappers.proto";
+            
+            message Event {
+                google.protobuf.Timestamp created_at = 1;
+                google.protobuf.Duration timeout = 2;
+                google.protobuf.StringValue nickname = 3;
+                google.protobuf.Struct attributes = 4;
+                repeated google.protobuf.Value values = 5;
+            }
+        "#;
+        
+        let proto_file = parse_proto_file(proto_content).unwrap();
+        let zod_metadata = ZodAnnotationParser::parse_file(&proto_file, proto_content);
+        let generator = ZodGenerator::new(zod_metadata, ZodGeneratorConfig::default());
+        let result = generator.generate(&proto_file);
+        let content = result.values().next().unwrap();
+        
+        assert!(content.contains("  created_at: z.string().datetime({ offset: true }).optional(),"));
+        assert!(content.contains(concat!(
+            "  timeout: z\n",
+            "    .string()\n",
+            r"    .regex(/^-?\d+(\.\d{1,9})?s$/)",
+            "\n    .optional(),\n",
+        )));
+        assert!(content.contains("  nickname: z.string().nullable().optional(),"));
+        assert!(content.contains("  attributes: z.record(z.string(), ProtoJsonValue).optional(),"));
+        assert!(content.contains("  values: ProtoJsonValue.array(),"));
+        assert!(content.find("const ProtoJsonValue: z.ZodType<ProtoJsonValue>").unwrap()
+            < content.find("export const Event =").unwrap());
+        
+        // Configured mappings
+        let mut well_known_types = WellKnownTypesConfig {
+            timestamp: TimestampMapping::Date,
+            r#struct: StructMapping::Unknown,
+            ..WellKnownTypesConfig::default()
+        };
+        well_known_types.overrides.insert(
+            "google.protobuf.Duration".to_string(),
+            "z.string().min(2)".to_string(),
+        );
+        let config = ZodGeneratorConfig {
+            well_known_types,
+            ..ZodGeneratorConfig::default()
+        };
+        let zod_metadata = ZodAnnotationParser::parse_file(&proto_file, proto_content);
+        let generator = ZodGenerator::new(zod_metadata, config);
+        let result = generator.generate(&proto_file);
+        let content = result.values().next().unwrap();
+        
+        assert!(content.contains(concat!(
+            "  created_at: z\n",
+            "    .string()\n",
+            "    .datetime({ offset: true })\n",
+            "    .pipe(z.coerce.date())\n",
+            "    .optional(),\n",
+        )));
+        assert!(content.contains("  timeout: z.string().min(2).optional(),"));
+        assert!(content.contains("  attributes: z.record(z.string(), z.unknown()).optional(),"));
+        assert!(!content.contains("ProtoJsonValue"));
+    }
+    
+    #[test]
+    fn test_integer_ranges() {
+        let proto_content = r#"
+            syntax = "proto3";
+            
+            message Counter {
+                int32 delta = 1;
+                uint32 count = 2; // @zod { max: 100 }
+                int64 total = 3;
+                fixed64 bytes_sent = 4;
+            }
+        "#;
+        
+        let proto_file = parse_proto_file(proto_content).unwrap();
+        let generate = |int64_strategy| {
+            let zod_metadata = ZodAnnotationParser::parse_file(&proto_file, proto_content);
+            let config = ZodGeneratorConfig { int64_strategy, ..ZodGeneratorConfig::default() };
+            let result = ZodGenerator::new(zod_metadata, config).generate(&proto_file);
+            result.into_values().next().unwrap()
+        };
+        
+        let content = generate(Int64Strategy::String);
+        assert!(content.contains("  delta: z.number().int().min(-2147483648).max(2147483647).optional(),"));
+        assert!(content.contains("  count: z.number().int().min(0).max(100).optional(),"));
+        assert!(content.contains(concat!(
+            "  total: z\n",
+            "    .string()\n",
+            r"    .regex(/^-?\d+$/)",
+            "\n",
+            "    .refine(\n",
+            "      (value) =>\n",
+            "        BigInt(value) >= -9223372036854775808n &&\n",
+            "        BigInt(value) <= 9223372036854775807n,\n",
+            "      {\n",
+            "        message: \"Must be between -9223372036854775808 and 9223372036854775807\",\n",
+            "      },\n",
+            "    )\n",
+            "    .optional(),\n",
+        )));
+        assert!(content.contains(concat!(
+            "  bytes_sent: z\n",
+            "    .string()\n",
+            r"    .regex(/^\d+$/)",
+            "\n",
+            "    .refine(\n",
+            "      (value) => BigInt(value) >= 0n && BigInt(value) <= 18446744073709551615n,\n",
+            "      { message: \"Must be between 0 and 18446744073709551615\" },\n",
+            "    )\n",
+        )));
+        
+        let content = generate(Int64Strategy::Bigint);
+        assert!(content.contains(concat!(
+            "  total: z\n",
+            "    .bigint()\n",
+            "    .gte(-9223372036854775808n)\n",
+            "    .lte(9223372036854775807n)\n",
+            "    .optional(),\n",
+        )));
+        assert!(content.contains("  bytes_sent: z.bigint().gte(0n).lte(18446744073709551615n).optional(),"));
+        
+        let content = generate(Int64Strategy::Union);
+        assert!(content.contains(concat!(
+            "  bytes_sent: z\n",
+            r"    .union([z.string().regex(/^\d+$/), z.number().int()])",
+            "\n",
+            "    .pipe(z.coerce.bigint().gte(0n).lte(18446744073709551615n))\n",
+            "    .optional(),\n",
+        )));
+    }
+    
+    #[test]
+    fn test_json_mapping() {
+        let proto_content = r#"
+            syntax = "proto3";
+            
+            enum Status {
+                STATUS_UNSPECIFIED = 0;
+                STATUS_ACTIVE = 1;
+            }
+            
+            message Address // @zod { refinements: { rule: "equals", field: "billing_zip", other: "zip_code" } }
+            {
+                string zip_code = 1;
+                string street_name = 2 [json_name = "street"];
+                bytes avatar = 3;
+                Status status = 4;
+                string billing_zip = 5;
+            }
+        "#;
+        
+        let proto_file = parse_proto_file(proto_content).unwrap();
+        let generate = |accept_proto_names| {
+            let zod_metadata = ZodAnnotationParser::parse_file(&proto_file, proto_content);
+            let config = ZodGeneratorConfig {
+                json_mapping: JsonMappingConfig { enabled: true, accept_proto_names },
+                ..ZodGeneratorConfig::default()
+            };
+            let result = ZodGenerator::new(zod_metadata, config).generate(&proto_file);
+            result.into_values().next().unwrap()
+        };
+        
+        let content = generate(false);
+        assert!(content.contains(
+            "export const Status = z.union([\n  z.enum([\"STATUS_UNSPECIFIED\", \"STATUS_ACTIVE\"]),\n  z.literal(0),\n"
+        ));
+        assert!(content.contains("export const Address = z\n  .object({\n    zipCode: z.string().optional(),"));
+        assert!(content.contains("    street: z.string().optional(),"));
+        assert!(content.contains("    avatar: z\n      .string()\n      .regex(/^[A-Za-z0-9+/_-]*={0,2}$/)\n"));
+        assert!(content.contains("    if (data[\"billingZip\"] !== data[\"zipCode\"]) {"));
+        assert!(!content.contains("acceptProtoNames"));
+        
+        let content = generate(true);
+        assert!(content.contains("function acceptProtoNames(names: Record<string, string>) {\n  return (value: unknown) => {\n"));
+        assert!(content.contains(concat!(
+            "export const Address = z.preprocess(\n",
+            "  acceptProtoNames({\n",
+            "    zip_code: \"zipCode\",\n",
+            "    street_name: \"street\",\n",
+            "    billing_zip: \"billingZip\",\n",
+            "  }),\n",
+            "  z\n",
+            "    .object({\n",
+        )));
+        
+        // Files of a package share one copy of the helper
+        let zod_metadata = ZodAnnotationParser::parse_file(&proto_file, proto_content);
+        let config = ZodGeneratorConfig {
+            single_file: false,
+            json_mapping: JsonMappingConfig { enabled: true, accept_proto_names: true },
+            ..ZodGeneratorConfig::default()
+        };
+        let result = ZodGenerator::new(zod_metadata, config).generate(&proto_file);
+        let helper = body(&result["default/protoNames.ts"]);
+        assert!(helper.starts_with("export function acceptProtoNames(names: Record<string, string>) {\n"));
+        let address = &result["default/Address.ts"];
+        assert!(address.contains("import { acceptProtoNames } from \"./protoNames\";\n"));
+        assert!(!address.contains("function acceptProtoNames"));
+        assert!(!result["default/Status.ts"].contains("acceptProtoNames"));
+        assert!(!result["default/index.ts"].contains("protoNames"));
+    }
+    
+    #[test]
+    fn test_enum_styles() {
+        let proto_content = r#"
+            syntax = "proto3";
+            
+            enum Priority {
+                option allow_alias = true;
+                PRIORITY_LOW = 0;
+                PRIORITY_DEFAULT = 0;
+                PRIORITY_HIGH = 2;
+            }
+        "#;
+        
+        let proto_file = parse_proto_file(proto_content).unwrap();
+        let generate = |enum_style, open_enums| {
+            let zod_metadata = ZodAnnotationParser::parse_file(&proto_file, proto_content);
+            let config = ZodGeneratorConfig { enum_style, open_enums, ..ZodGeneratorConfig::default() };
+            let result = ZodGenerator::new(zod_metadata, config).generate(&proto_file);
+            result.into_values().next().unwrap()
+        };
+        
+        let content = generate(EnumStyle::Names, false);
+        assert!(content.contains(concat!(
+            "export const Priority = z.enum([\n",
+            "  \"PRIORITY_LOW\",\n",
+            "  \"PRIORITY_DEFAULT\",\n",
+            "  \"PRIORITY_HIGH\",\n",
+            "]);\n",
+        )));
+        
+        let content = generate(EnumStyle::TsEnum, false);
+        assert!(content.contains(concat!(
+            "export enum PriorityEnum {\n",
+            "  PRIORITY_LOW = 0,\n",
+            "  PRIORITY_DEFAULT = 0,\n",
+            "  PRIORITY_HIGH = 2,\n",
+            "}\n",
+            "export const Priority = z.nativeEnum(PriorityEnum);\n",
+        )));
+        
+        let content = generate(EnumStyle::ConstObject, false);
+        assert!(content.contains("export const PriorityValues = {\n  PRIORITY_LOW: 0,\n"));
+        assert!(content.contains("} as const;\nexport const Priority = z.nativeEnum(PriorityValues);"));
+        
+        // Aliases are accepted once as a number
+        let content = generate(EnumStyle::Union, true);
+        assert!(content.contains(concat!(
+            "export const Priority = z.union([\n",
+            "  z.enum([\"PRIORITY_LOW\", \"PRIORITY_DEFAULT\", \"PRIORITY_HIGH\"]),\n",
+            "  z.literal(0),\n",
+            "  z.literal(2),\n",
+            "  z.number().int(),\n",
+            "]);\n",
+        )));
+    }
+    
+    #[test]
+    fn test_map_keys() {
+        let proto_content = r#"
+            syntax = "proto3";
+            
+            import "google/protobuf/timestamp.proto";
+            
+            enum Region {
+                REGION_EU = 0;
+                REGION_US = 1;
+            }
+            
+            message Inventory {
+                message Slot {
+                    int32 quantity = 1;
+                }
+                
+                map<string, string> labels = 1;
+                map<int64, Slot> slots = 2;
+                map<uint32, google.protobuf.Timestamp> restocked = 3;
+                map<bool, int32> flags = 4;
+                map<Region, Inventory.Slot> regional = 5;
+            }
+        "#;
+        
+        let proto_file = parse_proto_file(proto_content).unwrap();
+        let zod_metadata = ZodAnnotationParser::parse_file(&proto_file, proto_content);
+        let generator = ZodGenerator::new(zod_metadata, ZodGeneratorConfig::default());
+        let result = generator.generate(&proto_file);
+        let content = result.values().next().unwrap();
+        
+        assert!(content.contains("  labels: z.record(z.string(), z.string()).optional(),"));
+        assert!(content.contains(r"  slots: z.record(z.string().regex(/^-?\d+$/), Inventory_Slot).optional(),"));
+        assert!(content.contains(concat!(
+            "  restocked: z\n",
+            r"    .record(z.string().regex(/^\d+$/), z.string().datetime({ offset: true }))",
+            "\n    .optional(),\n",
+        )));
+        assert!(content.contains(concat!(
+            "  flags: z\n",
+            "    .record(\n",
+            "      z.enum([\"true\", \"false\"]),\n",
+            "      z.number().int().min(-2147483648).max(2147483647),\n",
+            "    )\n",
+            "    .optional(),\n",
+        )));
+        assert!(content.contains("  regional: z.record(Region, Inventory_Slot).optional(),"));
+    }
+    
+    #[test]
+    fn test_field_presence() {
+        let proto_content = r#"
+            syntax = "proto3";
+            
+            enum Role {
+                ROLE_UNSPECIFIED = 0;
+                ROLE_ADMIN = 1;
+            }
+            
+            message Profile {
+                string name = 1;
+                optional string nickname = 2;
+                Profile manager = 3;
+                repeated string tags = 4;
+                Role role = 5;
+                map<string, int32> scores = 6;
+                bool active = 7;
+            }
+        "#;
+        
+        let proto_file = parse_proto_file(proto_content).unwrap();
+        let generate = |field_presence| {
+            let zod_metadata = ZodAnnotationParser::parse_file(&proto_file, proto_content);
+            let config = ZodGeneratorConfig { field_presence, ..ZodGeneratorConfig::default() };
+            let result = ZodGenerator::new(zod_metadata, config).generate(&proto_file);
+            result.into_values().next().unwrap()
+        };
+        
+        let content = generate(PresenceStrategy::Partial);
+        assert!(content.contains("  name?: string;\n  nickname?: string;\n  manager?: Profile;\n  tags: string[];\n"));
+        assert!(content.contains("  name: z.string().optional(),\n  nickname: z.string().optional(),"));
+        
+        let content = generate(PresenceStrategy::Explicit);
+        assert!(content.contains("  name: string;\n  nickname?: string;\n  manager?: Profile;\n  tags: string[];\n"));
+        assert!(content.contains("  name: z.string(),\n  nickname: z.string().optional(),"));
+        assert!(content.contains("  role: Role,"));
+        
+        let content = generate(PresenceStrategy::ZeroDefaults);
+        assert!(content.contains("  name: z.string().default(\"\"),\n  nickname: z.string().optional(),"));
+        assert!(content.contains("  manager: z.lazy(() => Profile).optional(),"));
+        assert!(content.contains("  tags: z.string().array().default([]),"));
+        assert!(content.contains("  role: Role.default(\"ROLE_UNSPECIFIED\"),"));
+        assert!(content.contains(concat!(
+            "  scores: z\n",
+            "    .record(z.string(), z.number().int().min(-2147483648).max(2147483647))\n",
+            "    .default({}),\n",
+        )));
+        assert!(content.contains("  active: z.boolean().default(false),"));
+    }
+    
+    #[test]
+    fn test_multi_file_output() {
+        let proto_content = r#"
+            syntax = "proto3";
+            package shop.v1;
+            
+            enum Status {
+                STATUS_UNSPECIFIED = 0;
+            }
+            
+            message Order {
+                message Line {
+                    string sku = 1;
+                }
+                
+                repeated Line lines = 1;
+                Customer customer = 2;
+                Status status = 3;
+            }
+            
+            message Customer {
+                string name = 1;
+            }
+        "#;
+        
+        let proto_file = parse_proto_file(proto_content).unwrap();
+        let zod_metadata = ZodAnnotationParser::parse_file(&proto_file, proto_content);
+        let config = ZodGeneratorConfig { single_file: false, ..ZodGeneratorConfig::default() };
+        let result = ZodGenerator::new(zod_metadata, config).generate(&proto_file);
+        
+        let filenames: Vec<&str> = result.keys().map(String::as_str).collect();
+        assert_eq!(filenames, vec![
+            "shop_v1/Customer.ts",
+            "shop_v1/Order.ts",
+            "shop_v1/Status.ts",
+            "shop_v1/index.ts",
+        ]);
+        
+        let order = &result["shop_v1/Order.ts"];
+        assert!(body(order).starts_with(concat!(
+            "import { z } from \"zod\";\n",
+            "import { Customer } from \"./Customer\";\n",
+            "import { Status } from \"./Status\";\n",
+        )));
+        assert!(order.find("export const Order_Line =").unwrap() < order.find("export const Order =").unwrap());
+        assert!(!result["shop_v1/Customer.ts"].contains("import { Order"));
+        
+        assert_eq!(
+            body(&result["shop_v1/index.ts"]),
+            "export * from \"./Customer\";\nexport * from \"./Order\";\nexport * from \"./Status\";\n"
+        );
+    }
+    
+    #[test]
+    fn test_cross_file_imports() {
+        let money_content = r#"
+            syntax = "proto3";
+            package shop.common;
+            
+            message Money {
+                enum Currency {
+                    CURRENCY_UNSPECIFIED = 0;
+                    EUR = 1;
+                }
+                
+                int64 units = 1;
+                Currency currency = 2;
+            }
+        "#;
+        let proto_content = r#"
+            syntax = "proto3";
+            package shop.v1;
+            
+            import "shop/common/money.proto";
+            import "google/protobuf/timestamp.proto";
+            
+            message Order {
+                common.Money total = 1;
+                shop.common.Money.Currency currency = 2;
+                map<string, .shop.common.Money> taxes = 3;
+                google.protobuf.Timestamp created_at = 4;
+            }
+        "#;
+        
+        let imported_files = vec![ImportedFile {
+            path: "shop/common/money.proto".to_string(),
+            proto_file: parse_proto_file(money_content).unwrap(),
+        }];
+        let proto_file = parse_proto_file(proto_content).unwrap();
+        let generate = |config: ZodGeneratorConfig| {
+            let zod_metadata = ZodAnnotationParser::parse_file(&proto_file, proto_content);
+            ZodGenerator::new(zod_metadata, config)
+                .with_imports("shop/v1/order.proto", &imported_files)
+                .generate(&proto_file)
+        };
+        
+        let result = generate(ZodGeneratorConfig::default());
+        let content = &result["shop/v1/order.ts"];
+        assert!(body(content).starts_with(concat!(
+            "import { z } from \"zod\";\n",
+            "import { Money, Money_Currency } from \"../common/money\";\n",
+        )));
+        assert!(content.contains("  total: Money.optional(),"));
+        assert!(content.contains("  currency: Money_Currency.optional(),"));
+        assert!(content.contains("  taxes: z.record(z.string(), Money).optional(),"));
+        assert!(content.contains("  created_at: z.string().datetime({ offset: true }).optional(),"));
+        
+        // Namespaced nested types are reached through their top-level type
+        let result = generate(ZodGeneratorConfig {
+            nested_naming: NestedTypeNaming::Namespace,
+            ..ZodGeneratorConfig::default()
+        });
+        let content = &result["shop/v1/order.ts"];
+        assert!(content.contains("import { Money } from \"../common/money\";\n"));
+        assert!(content.contains("  currency: Money.Currency.optional(),"));
+        
+        // Aliases replace relative paths; multi-file output is one level deeper
+        let result = generate(ZodGeneratorConfig {
+            single_file: false,
+            import_aliases: [("shop/common/".to_string(), "@shop/common/".to_string())].into(),
+            ..ZodGeneratorConfig::default()
+        });
+        assert!(result["shop/v1/order/Order.ts"]
+            .contains("import { Money, Money_Currency } from \"@shop/common/money\";\n"));
+        assert!(result.contains_key("shop/v1/order/index.ts"));
+        
+        let result = generate(ZodGeneratorConfig { single_file: false, ..ZodGeneratorConfig::default() });
+        assert!(result["shop/v1/order/Order.ts"]
+            .contains("import { Money, Money_Currency } from \"../../common/money\";\n"));
+        
+        // Types come along with suffixed schemas only where types are written out
+        let result = generate(ZodGeneratorConfig { schema_suffix: "Schema".to_string(), ..ZodGeneratorConfig::default() });
+        assert!(result["shop/v1/order.ts"]
+            .contains("import { MoneySchema, Money_CurrencySchema } from \"../common/money\";\n"));
+        let result = generate(ZodGeneratorConfig {
+            schema_suffix: "Schema".to_string(),
+            type_declarations: TypeDeclarations::Explicit,
+            ..ZodGeneratorConfig::default()
+        });
+        assert!(result["shop/v1/order.ts"].contains(concat!(
+            "import {\n",
+            "  MoneySchema,\n",
+            "  Money_CurrencySchema,\n",
+            "  type Money,\n",
+            "  type Money_Currency,\n",
+            "} from \"../common/money\";\n",
+        )));
+    }
+    
+    #[test]
+    fn test_imports_from_proto_paths() {
+        let workspace = tempfile::tempdir().unwrap();
+        fs::create_dir_all(workspace.path().join("shop")).unwrap();
+        fs::create_dir_all(workspace.path().join("common")).unwrap();
+        fs::write(workspace.path().join("common/money.proto"), r#"
+            syntax = "proto3";
+            package common;
+            
+            message Money {
+                int64 units = 1;
+            }
+        "#).unwrap();
+        let proto_content = r#"
+            syntax = "proto3";
+            package shop;
+            
+            import "common/money.proto";
+            
+            message Order {
+                common.Money total = 1;
+            }
+        "#;
+        fs::write(workspace.path().join("shop/order.proto"), proto_content).unwrap();
+        
+        // As the command line resolves `-i <input> [-I <proto path>]`, from the workspace
+        let generate = |input: &str, include_paths: &[&str]| {
+            let include_paths: Vec<PathBuf> = include_paths.iter().map(PathBuf::from).collect();
+            let source = source_path(Path::new(input), &include_paths, workspace.path())?;
+            let include_paths: Vec<PathBuf> = include_paths.iter().map(|path| workspace.path().join(path)).collect();
+            let proto_file = parse_proto_file(proto_content).unwrap();
+            let imported_files = load_imports(&proto_file, &include_paths);
+            let zod_metadata = ZodAnnotationParser::parse_file(&proto_file, proto_content);
+            Ok::<_, String>(ZodGenerator::new(zod_metadata, ZodGeneratorConfig::default())
+                .with_imports(&source, &imported_files)
+                .generate(&proto_file))
+        };
+        
+        // `-i shop/order.proto -I .`, and without `-I`, which defaults to the current directory
+        for (input, include_paths) in [("shop/order.proto", vec!["."]), ("./shop/order.proto", vec!["."])] {
+            let result = generate(input, &include_paths).unwrap();
+            let filenames: Vec<&str> = result.keys().map(String::as_str).collect();
+            assert_eq!(filenames, vec!["shop/order.ts"]);
+            assert!(result["shop/order.ts"].contains("import { Money } from \"../common/money\";\n"));
+        }
+        
+        assert!(generate("shop/order.proto", &["common"]).is_err());
+    }
+    
+    #[test]
+    fn test_service_descriptors_and_client() {
+        let proto_content = r#"
+            syntax = "proto3";
+            package chat.v1;
+            
+            import "google/protobuf/empty.proto";
+            
+            message Message {
+                string text = 1;
+            }
+            
+            service ChatService {
+                rpc Send(Message) returns (google.protobuf.Empty);
+                rpc Subscribe(google.protobuf.Empty) returns (stream Message);
+                rpc Upload(stream Message) returns (google.protobuf.Empty);
+            }
+        "#;
+        
+        let proto_file = parse_proto_file(proto_content).unwrap();
+        let generate = |config: ZodGeneratorConfig| {
+            let zod_metadata = ZodAnnotationParser::parse_file(&proto_file, proto_content);
+            ZodGenerator::new(zod_metadata, config).generate(&proto_file)
+        };
+        
+        let content = generate(ZodGeneratorConfig::default()).into_values().next().unwrap();
+        assert!(content.contains(concat!(
+            "export const ChatService = {\n",
+            "  typeName: \"chat.v1.ChatService\",\n",
+            "  methods: {\n",
+            "    send: {\n",
+            "      name: \"Send\",\n",
+            "      input: Message,\n",
+            "      output: z.object({}),\n",
+            "      kind: \"unary\",\n",
+            "    },\n",
+        )));
+        assert!(content.contains("      kind: \"server_streaming\",\n"));
+        assert!(content.contains("      kind: \"client_streaming\",\n"));
+        assert!(content.find("export const Message =").unwrap() < content.find("export const ChatService =").unwrap());
+        assert!(!content.contains("createChatServiceClient"));
+        assert!(!content.contains("connectUnary"));
+        
+        let content = generate(ZodGeneratorConfig { connect_client: true, ..ZodGeneratorConfig::default() })
+            .into_values().next().unwrap();
+        assert!(content.contains("export async function connectUnary<M extends ConnectMethod>("));
+        assert!(content.contains(concat!(
+            "export function createChatServiceClient(options: ConnectTransportOptions) {\n",
+            "  return {\n",
+            "    send: (request: z.input<typeof ChatService.methods.send.input>, signal?: AbortSignal) =>\n",
+            "      connectUnary(options, ChatService, ChatService.methods.send, request, signal),\n",
+            "    subscribe: (request: z.input<typeof ChatService.methods.subscribe.input>, signal?: AbortSignal) =>\n",
+            "      connectServerStream(options, ChatService, ChatService.methods.subscribe, request, signal),\n",
+            "  };\n",
+            "}",
+        )));
+        assert!(!content.contains("    upload: (request"));
+        
+        let result = generate(ZodGeneratorConfig {
+            single_file: false,
+            connect_client: true,
+            ..ZodGeneratorConfig::default()
+        });
+        assert!(body(&result["chat_v1/ChatService.ts"]).starts_with(concat!(
+            "import { z } from \"zod\";\n",
+            "import {\n",
+            "  ConnectTransportOptions,\n",
+            "  connectUnary,\n",
+            "  connectServerStream,\n",
+            "} from \"./connect\";\n",
+            "import { Message } from \"./Message\";\n",
+        )));
+        assert!(result["chat_v1/connect.ts"].contains("export class ConnectError extends Error {"));
+        assert_eq!(
+            body(&result["chat_v1/index.ts"]),
+            "export * from \"./Message\";\nexport * from \"./ChatService\";\nexport * from \"./connect\";\n"
+        );
+    }
+    
+    #[test]
+    fn test_unknown_keys() {
+        let proto_content = r#"
+            syntax = "proto3";
+            
+            message CreateOrder {
+                string sku = 1;
+            }
+            
+            message OrderPlaced // @zod { unknown_keys: "passthrough" }
+            {
+                string sku = 1;
+            }
+            
+            message Labels // @zod { unknown_keys: { catchall: "z.string()" } }
+            {
+                string name = 1;
+            }
+            
+            message Contact {
+                oneof channel {
+                    string email = 1;
+                    string phone = 2;
+                }
+            }
+        "#;
+        
+        let proto_file = parse_proto_file(proto_content).unwrap();
+        let generate = |config: ZodGeneratorConfig| {
+            let zod_metadata = ZodAnnotationParser::parse_file(&proto_file, proto_content);
+            ZodGenerator::new(zod_metadata, config).generate(&proto_file).into_values().next().unwrap()
+        };
+        
+        let content = generate(ZodGeneratorConfig::default());
+        assert!(content.contains("  sku: z.string().optional(),\n});\nexport type CreateOrder"));
+        assert!(content.contains("    sku: z.string().optional(),\n  })\n  .passthrough();"));
+        assert!(content.contains("    name: z.string().optional(),\n  })\n  .catchall(z.string());"));
+        
+        let content = generate(ZodGeneratorConfig {
+            unknown_keys: UnknownKeys::Strict,
+            oneof_style: OneofStyle::Union,
+            ..ZodGeneratorConfig::default()
+        });
+        assert!(content.contains("    sku: z.string().optional(),\n  })\n  .strict();\nexport type CreateOrder"));
+        assert!(content.contains("    sku: z.string().optional(),\n  })\n  .passthrough();"));
+        // Oneof members are let through the strict object and checked by the variants
+        assert!(content.contains(concat!(
+            "export const Contact = z\n",
+            "  .object({\n",
+            "    email: z.unknown().optional(),\n",
+            "    phone: z.unknown().optional(),\n",
+            "  })\n",
+            "  .strict()\n",
+            "  .and(\n",
+            "    z.union([\n",
+        )));
+    }
+    
+    #[test]
+    fn test_zod_versions() {
+        let proto_content = r#"
+            syntax = "proto3";
+            
+            enum Color {
+                RED = 0;
+                GREEN = 1;
+            }
+            
+            message User // @zod { unknown_keys: "strict" }
+            {
+                string email = 1; // @zod { email: true, max: 100, description: "Contact address" }
+                int32 age = 2; // @zod { min: 0 }
+                map<bool, string> flags = 3;
+                repeated string tags = 4; // @zod { array: { min: 1 } }
+            }
+        "#;
+        
+        let proto_file = parse_proto_file(proto_content).unwrap();
+        let generate = |config: ZodGeneratorConfig| {
+            let zod_metadata = ZodAnnotationParser::parse_file(&proto_file, proto_content);
+            ZodGenerator::new(zod_metadata, config).generate(&proto_file).into_values().next().unwrap()
+        };
+        
+        let content = generate(ZodGeneratorConfig::default());
+        assert!(content.contains("  email: z.string().max(100).email().describe(\"Contact address\").optional(),"));
+        assert!(content.contains("  flags: z.record(z.enum([\"true\", \"false\"]), z.string()).optional(),"));
+        
+        let content = generate(ZodGeneratorConfig {
+            zod_version: ZodVersion::V4,
+            enum_style: EnumStyle::TsEnum,
+            ..ZodGeneratorConfig::default()
+        });
+        assert!(body(&content).starts_with("import { z } from \"zod\";"));
+        assert!(content.contains("export const Color = z.enum(ColorEnum);"));
+        assert!(content.contains("export const User = z.strictObject({"));
+        assert!(content.contains("  email: z.email().max(100).meta({ description: \"Contact address\" }).optional(),"));
+        assert!(content.contains("  flags: z.partialRecord(z.enum([\"true\", \"false\"]), z.string()).optional(),"));
+        
+        let content = generate(ZodGeneratorConfig {
+            zod_version: ZodVersion::Mini,
+            ..ZodGeneratorConfig::default()
+        });
+        assert!(body(&content).starts_with("import { z } from \"zod/mini\";"));
+        assert!(content.contains(concat!(
+            "  email: z.optional(\n",
+            "    z\n",
+            "      .email()\n",
+            "      .check(z.maxLength(100))\n",
+            "      .register(z.globalRegistry, { description: \"Contact address\" }),\n",
+            "  ),\n",
+        )));
+        assert!(content.contains("  age: z.optional(z.int().check(z.gte(0)).check(z.lte(2147483647))),"));
+        assert!(content.contains("  tags: z.array(z.string()).check(z.minLength(1)),"));
+    }
+    
+    #[test]
+    fn test_type_declarations_and_brands() {
+        let proto_content = r#"
+            syntax = "proto3";
+            
+            enum Role {
+                ADMIN = 0;
+                MEMBER = 1;
+            }
+            
+            message User {
+                string id = 1; // @zod { brand: "UserId" }
+                Role role = 2;
+                repeated string friend_ids = 3; // @zod { brand: "UserId" }
+            }
+            
+            message Team {
+                repeated User members = 1;
+            }
+        "#;
+        
+        let proto_file = parse_proto_file(proto_content).unwrap();
+        let generate = |config: ZodGeneratorConfig| {
+            let zod_metadata = ZodAnnotationParser::parse_file(&proto_file, proto_content);
+            ZodGenerator::new(zod_metadata, config).generate(&proto_file)
+        };
+        
+        let files = generate(ZodGeneratorConfig {
+            type_declarations: TypeDeclarations::Explicit,
+            schema_suffix: "Schema".to_string(),
+            ..ZodGeneratorConfig::default()
+        });
+        let content = files.into_values().next().unwrap();
+        assert!(content.contains("export type Role = \"ADMIN\" | \"MEMBER\";\nexport const RoleSchema = z.enum("));
+        assert!(content.contains(concat!(
+            "export interface User {\n",
+            "  id?: string & z.BRAND<\"UserId\">;\n",
+            "  role?: Role;\n",
+            "  friend_ids: (string & z.BRAND<\"UserId\">)[];\n",
+            "}\n",
+            "export const UserSchema = z.object({\n",
+            "  id: z.string().brand<\"UserId\">().optional(),\n",
+            "  role: RoleSchema.optional(),\n",
+        )));
+        assert!(content.contains("  members: UserSchema.array(),"));
+        assert!(!content.contains("z.infer"));
+        
+        // Suffixed schemas are imported with their types where written out types use them
+        let files = generate(ZodGeneratorConfig {
+            type_declarations: TypeDeclarations::Explicit,
+            schema_suffix: "Schema".to_string(),
+            single_file: false,
+            ..ZodGeneratorConfig::default()
+        });
+        assert!(files["default/Team.ts"].contains("import { UserSchema, type User } from \"./User\";"));
+        
+        let files = generate(ZodGeneratorConfig { generate_types: false, ..ZodGeneratorConfig::default() });
+        let content = files.into_values().next().unwrap();
+        assert!(content.contains("export const User = z.object({"));
+        assert!(!content.contains("export type"));
+    }
+    
+    #[test]
+    fn test_domain_types() {
+        let proto_content = r#"
+            syntax = "proto3";
+            
+            import "google/protobuf/timestamp.proto";
+            
+            enum Role {
+                ADMIN = 0;
+                MEMBER = 1;
+            }
+            
+            message User {
+                int64 balance = 1;
+                bytes avatar = 2;
+                google.protobuf.Timestamp created_at = 3;
+                Role role = 4;
+            }
+            
+            service Users {
+                rpc Update(User) returns (User);
+            }
+        "#;
+        
+        let proto_file = parse_proto_file(proto_content).unwrap();
+        let generate = |zod_version, field_presence| {
+            let zod_metadata = ZodAnnotationParser::parse_file(&proto_file, proto_content);
+            let config = ZodGeneratorConfig {
+                domain_types: true,
+                zod_version,
+                field_presence,
+                ..ZodGeneratorConfig::default()
+            };
+            ZodGenerator::new(zod_metadata, config).generate(&proto_file).into_values().next().unwrap()
+        };
+        
+        let content = generate(ZodVersion::V3, PresenceStrategy::default());
+        assert!(content.contains("const protoBytesFromBase64 = (value: string) => {"));
+        assert!(content.contains(concat!(
+            "export const User = z.object({\n",
+            "  balance: z\n",
+            "    .union([z.string().regex(/^-?\\d+$/), z.number().int()])\n",
+            "    .pipe(\n",
+            "      z.coerce.bigint().gte(-9223372036854775808n).lte(9223372036854775807n),\n",
+            "    )\n",
+            "    .optional(),\n",
+            "  avatar: z\n",
+            "    .string()\n",
+            "    .regex(/^[A-Za-z0-9+/_-]*={0,2}$/)\n",
+            "    .transform(protoBytesFromBase64)\n",
+            "    .optional(),\n",
+            "  created_at: z\n",
+            "    .string()\n",
+            "    .datetime({ offset: true })\n",
+            "    .pipe(z.coerce.date())\n",
+            "    .optional(),\n",
+            "  role: Role.optional(),\n",
+            "});\n",
+            "export type User = z.output<typeof User>;\n",
+            "export type UserWire = z.input<typeof User>;\n",
+        )));
+        assert!(content.contains(concat!(
+            "export const UserToWire = z.object({\n",
+            "  balance: z\n",
+            "    .bigint()\n",
+            "    .gte(-9223372036854775808n)\n",
+            "    .lte(9223372036854775807n)\n",
+            "    .transform((value) => value.toString())\n",
+            "    .optional(),\n",
+            "  avatar: z.instanceof(Uint8Array).transform(protoBytesToBase64).optional(),\n",
+            "  created_at: z\n",
+            "    .date()\n",
+            "    .transform((value) => value.toISOString())\n",
+            "    .optional(),\n",
+            "  role: RoleToWire.optional(),\n",
+            "});\n",
+        )));
+        assert!(content.contains("export const RoleToWire = z.enum([\"ADMIN\", \"MEMBER\"]);"));
+        assert!(content.contains("      input: UserToWire,\n      output: User,"));
+        
+        // Zod 4 defaults skip parsing, so zero values are parsed as inputs
+        let content = generate(ZodVersion::V4, PresenceStrategy::ZeroDefaults);
+        assert!(content.contains("      z.coerce.bigint().gte(-9223372036854775808n).lte(9223372036854775807n),\n    )\n    .prefault(\"0\"),"));
+        assert!(content.contains(concat!(
+            "  avatar: z\n",
+            "    .instanceof(Uint8Array)\n",
+            "    .transform(protoBytesToBase64)\n",
+            "    .prefault(new Uint8Array()),\n",
+        )));
+        
+        // Enum values are names in the domain with every style
+        for (enum_style, values) in [(EnumStyle::TsEnum, "RoleEnum"), (EnumStyle::ConstObject, "RoleValues")] {
+            let zod_metadata = ZodAnnotationParser::parse_file(&proto_file, proto_content);
+            let config = ZodGeneratorConfig { domain_types: true, enum_style, ..ZodGeneratorConfig::default() };
+            let content = ZodGenerator::new(zod_metadata, config).generate(&proto_file).into_values().next().unwrap();
+            assert!(content.contains(concat!(
+                "export const Role = z.union([\n",
+                "  z.literal(0).transform(() => \"ADMIN\" as const),\n",
+                "  z.literal(1).transform(() => \"MEMBER\" as const),\n",
+                "]);\n",
+            )));
+            assert!(content.contains(&format!(
+                "export const RoleToWire = z\n  .enum([\"ADMIN\", \"MEMBER\"])\n  .transform((value) => {}[value]);\n",
+                values
+            )));
+        }
+    }
+    
+    #[test]
+    fn test_typed_defaults() {
+        let proto_content = r#"
+            syntax = "proto2";
+            
+            enum Role {
+                ADMIN = 0;
+                MEMBER = 1;
+            }
+            
+            message User {
+                optional string name = 1 [default = "anon \"x\""];
+                optional int64 balance = 2 [default = -5];
+                optional double ratio = 3 [default = inf];
+                optional Role role = 4 [default = MEMBER];
+                optional bytes avatar = 5 [default = "ab"];
+                optional int32 count = 6; // @zod { default: "12" }
+                repeated Role roles = 7; // @zod { default: ["ADMIN", 1] }
+                optional int32 limit = 8 [default = 10]; // @zod { default: "many" }
+            }
+        "#;
+        
+        let proto_file = parse_proto_file(proto_content).unwrap();
+        let generate = |enum_style, int64_strategy| {
+            let zod_metadata = ZodAnnotationParser::parse_file(&proto_file, proto_content);
+            let config = ZodGeneratorConfig { enum_style, int64_strategy, ..ZodGeneratorConfig::default() };
+            ZodGenerator::new(zod_metadata, config).generate(&proto_file).into_values().next().unwrap()
+        };
+        
+        let content = generate(EnumStyle::Names, Int64Strategy::String);
+        assert!(content.contains("  name: z.string().default('anon \"x\"'),"));
+        assert!(content.contains(".default(\"-5\"),\n  ratio: z.number().default(Infinity),"));
+        assert!(content.contains("  role: Role.default(\"MEMBER\"),"));
+        assert!(content.contains("  avatar: z.string().default(\"YWI=\"),"));
+        assert!(content.contains("  count: z.number().int().min(-2147483648).max(2147483647).default(12),"));
+        assert!(content.contains("  roles: Role.array().default([\"ADMIN\", \"MEMBER\"]),"));
+        // Annotations that do not fit the type are ignored, along with the proto2 default they override
+        assert!(content.contains("  limit: z.number().int().min(-2147483648).max(2147483647).optional(),"));
+        
+        let content = generate(EnumStyle::TsEnum, Int64Strategy::Bigint);
+        assert!(content.contains(".default(-5n),"));
+        assert!(content.contains("  role: Role.default(1),"));
+    }
+    
+    #[test]
+    fn test_int64_defaults() {
+        let proto_content = r#"
+            syntax = "proto3";
+            
+            enum Role // @zod { default: "MEMBER" }
+            {
+                ADMIN = 0;
+                MEMBER = 1;
+            }
+            
+            message Limits // @zod { default: { "quota": "10" } }
+            {
+                int64 quota = 1; // @zod { default: 5 }
+                uint64 used = 2;
+            }
+        "#;
+        
+        let proto_file = parse_proto_file(proto_content).unwrap();
+        let generate = |zod_version, int64_strategy| {
+            let zod_metadata = ZodAnnotationParser::parse_file(&proto_file, proto_content);
+            let config = ZodGeneratorConfig {
+                zod_version,
+                int64_strategy,
+                field_presence: PresenceStrategy::ZeroDefaults,
+                ..ZodGeneratorConfig::default()
+            };
+            ZodGenerator::new(zod_metadata, config).generate(&proto_file).into_values().next().unwrap()
+        };
+        
+        // Defaults are inputs of the schema: `bigint`s only where the schema takes them
+        for (zod_version, int64_strategy, quota, used) in [
+            (ZodVersion::V3, Int64Strategy::String, ".default(\"5\")", ".default(\"0\")"),
+            (ZodVersion::V3, Int64Strategy::Bigint, ".default(5n)", ".default(0n)"),
+            (ZodVersion::V3, Int64Strategy::Union, ".default(\"5\")", ".default(\"0\")"),
+            (ZodVersion::V4, Int64Strategy::String, ".default(\"5\")", ".default(\"0\")"),
+            (ZodVersion::V4, Int64Strategy::Bigint, ".default(5n)", ".default(0n)"),
+            (ZodVersion::V4, Int64Strategy::Union, ".prefault(\"5\")", ".prefault(\"0\")"),
+        ] {
+            let content = generate(zod_version, int64_strategy);
+            assert!(content.contains(&format!("{},\n    used: ", quota)), "{:?} {:?}", zod_version, int64_strategy);
+            assert!(content.contains(&format!("{},\n  }})\n", used)), "{:?} {:?}", zod_version, int64_strategy);
+        }
+        
+        // Enum and message defaults are typed literals too, the latter parsed
+        // to fill in the defaults of the fields
+        let content = generate(ZodVersion::V3, Int64Strategy::Bigint);
+        assert!(content.contains("export const Role = z.enum([\"ADMIN\", \"MEMBER\"]).default(\"MEMBER\");"));
+        assert!(content.contains("  })\n  .default({ quota: 10n });"));
+        let content = generate(ZodVersion::V4, Int64Strategy::String);
+        assert!(content.contains("  })\n  .prefault({ quota: \"10\" });"));
+    }
+    
+    #[test]
+    fn test_deprecation() {
+        let proto_content = r#"
+            syntax = "proto3";
+            
+            enum Status {
+                STATUS_UNKNOWN = 0;
+                STATUS_ACTIVE = 1;
+                STATUS_DELETED = 3 [deprecated = true];
+            }
+            
+            message User {
+                option deprecated = true;
+                string name = 1;
+                string nickname = 2 [deprecated = true];
+                Status status = 3;
+            }
+        "#;
+        
+        let proto_file = parse_proto_file(proto_content).unwrap();
+        let generate = |enum_style, omit_deprecated| {
+            let zod_metadata = ZodAnnotationParser::parse_file(&proto_file, proto_content);
+            let config = ZodGeneratorConfig { enum_style, omit_deprecated, ..ZodGeneratorConfig::default() };
+            ZodGenerator::new(zod_metadata, config).generate(&proto_file).into_values().next().unwrap()
+        };
+        
+        let content = generate(EnumStyle::TsEnum, false);
+        assert!(content.contains("  STATUS_ACTIVE = 1,\n  /** @deprecated */\n  STATUS_DELETED = 3,\n"));
+        assert!(content.contains(concat!(
+            "/** @deprecated */\n",
+            "export const User = z.object({\n",
+            "  name: z.string().optional(),\n",
+            "  /** @deprecated */\n",
+            "  nickname: z.string().optional(),\n",
+        )));
+        assert!(content.contains("/** @deprecated */\nexport type User = z.infer<typeof User>;"));
+        
+        // Values of `z.enum` are listed in the enum's JSDoc instead
+        let content = generate(EnumStyle::Names, false);
+        assert!(content.contains(concat!(
+            "/**\n",
+            " * - `STATUS_DELETED`: Deprecated.\n",
+            " */\n",
+            "export const Status = z.enum([\n",
+        )));
+        
+        let content = generate(EnumStyle::Names, true);
+        assert!(content.contains("export const Status = z.enum([\"STATUS_UNKNOWN\", \"STATUS_ACTIVE\"]);"));
+        assert!(!content.contains("nickname"));
+    }
+    
+    #[test]
+    fn test_comments_as_jsdoc() {
+        let proto_content = r#"
+            syntax = "proto3";
+            
+            // Roles of a user
+            enum Role {
+                // Full access
+                ADMIN = 0;
+                MEMBER = 1;
+            }
+            
+            // A user of the system
+            message User // @zod { description: "Stored in the \"users\" table" }
+            {
+                // Display name
+                // shown to other users
+                string name = 1;
+                Role role = 2;
+            }
+        "#;
+        
+        let proto_file = parse_proto_file(proto_content).unwrap();
+        let generate = |enum_style, describe_comments| {
+            let zod_metadata = ZodAnnotationParser::parse_file(&proto_file, proto_content);
+            let config = ZodGeneratorConfig {
+                enum_style,
+                describe_comments,
+                ..ZodGeneratorConfig::default()
+            };
+            ZodGenerator::new(zod_metadata, config).generate(&proto_file).into_values().next().unwrap()
+        };
+        
+        let content = generate(EnumStyle::TsEnum, false);
+        assert!(content.contains("/**\n * Roles of a user\n */\nexport enum RoleEnum {\n  /**\n   * Full access\n   */\n  ADMIN = 0,"));
+        assert!(content.contains(concat!(
+            "/**\n",
+            " * A user of the system\n",
+            " * Stored in the \"users\" table\n",
+            " */\n",
+            "export const User = z\n",
+            "  .object({\n",
+            "    /**\n",
+            "     * Display name\n",
+            "     * shown to other users\n",
+            "     */\n",
+            "    name: z.string().optional(),\n",
+            "    role: Role.optional(),\n",
+            "  })\n",
+            "  .describe('Stored in the \"users\" table');",
+        )));
+        
+        // Comments describe the schemas without a description annotation
+        let content = generate(EnumStyle::TsEnum, true);
+        assert!(content.contains("  name: z.string().describe(\"Display name\\nshown to other users\").optional(),"));
+        assert!(content.contains("export const Role = z.nativeEnum(RoleEnum).describe(\"Roles of a user\");"));
+        assert!(content.contains("  })\n  .describe('Stored in the \"users\" table');"));
+        
+        // Values of `z.enum` are described in the enum's JSDoc
+        let content = generate(EnumStyle::Names, false);
+        assert!(content.contains("/**\n * Roles of a user\n *\n * - `ADMIN`: Full access\n */\nexport const Role = z.enum(["));
+    }
+    
+    #[test]
+    fn test_golden_output() {
+        let proto_content = r#"
+            syntax = "proto3";
+            package shop;
+            
+            // Order states
+            enum Status {
+                PENDING = 0;
+                SHIPPED = 1;
+            }
+            
+            message Order {
+                string id = 1; // @zod { uuid: true }
+                string note = 2; // @zod { max: 500, description: "Shown to the courier when it's delivered" }
+                repeated string tags = 3; // @zod { array: { min: 1, max: 10 } }
+                Status status = 4;
+                map<string, int32> quantities = 5;
+            }
+        "#;
+        
+        let proto_file = parse_proto_file(proto_content).unwrap();
+        let generate = || {
+            let zod_metadata = ZodAnnotationParser::parse_file(&proto_file, proto_content);
+            ZodGenerator::new(zod_metadata, ZodGeneratorConfig::default())
+                .with_imports("shop/order.proto", &[])
+                .generate(&proto_file)
+        };
+        
+        let files = generate();
+        let expected = format!(concat!(
+            "// Generated by protobuf_to_zod v{} from shop/order.proto. Do not edit.\n",
+            "// Content hash: 773d247f1a8e5547\n",
+            "\n",
+            "import {{ z }} from \"zod\";\n",
+            "\n",
+            "/**\n",
+            " * Order states\n",
+            " */\n",
+            "export const Status = z.enum([\"PENDING\", \"SHIPPED\"]);\n",
+            "export type Status = z.infer<typeof Status>;\n",
+            "\n",
+            "export const Order = z.object({{\n",
+            "  id: z.string().uuid().optional(),\n",
+            "  /**\n",
+            "   * Shown to the courier when it's delivered\n",
+            "   */\n",
+            "  note: z\n",
+            "    .string()\n",
+            "    .max(500)\n",
+            "    .describe(\"Shown to the courier when it's delivered\")\n",
+            "    .optional(),\n",
+            "  tags: z.string().array().min(1).max(10),\n",
+            "  status: Status.optional(),\n",
+            "  quantities: z\n",
+            "    .record(z.string(), z.number().int().min(-2147483648).max(2147483647))\n",
+            "    .optional(),\n",
+            "}});\n",
+            "export type Order = z.infer<typeof Order>;\n",
+        ), env!("CARGO_PKG_VERSION"));
+        assert_eq!(files["shop/order.ts"], expected);
+        
+        // Output is byte-for-byte the same on every run, and laying it out again leaves it unchanged
+        assert_eq!(generate(), files);
+        assert_eq!(layout(body(&expected)), body(&expected));
+    }
+    
+    #[test]
+    fn test_provenance_header() {
+        let generate = |proto_content: &str, config: ZodGeneratorConfig| {
+            let proto_file = parse_proto_file(proto_content).unwrap();
+            let zod_metadata = ZodAnnotationParser::parse_file(&proto_file, proto_content);
+            ZodGenerator::new(zod_metadata, config).generate(&proto_file)
+        };
+        let hash = |content: &str| content.lines().nth(1).unwrap().to_string();
+        
+        let proto_content = "syntax = \"proto3\";\npackage shop;\nmessage Order { string id = 1; }\nmessage Item { string sku = 1; }\n";
+        let content = generate(proto_content, ZodGeneratorConfig::default()).into_values().next().unwrap();
+        assert!(content.starts_with(&format!("// Generated by protobuf_to_zod v{}. Do not edit.\n", env!("CARGO_PKG_VERSION"))));
+        assert!(hash(&content).starts_with("// Content hash: "));
+        
+        // The hash follows the generated code
+        let changed = generate(&proto_content.replace("sku", "code"), ZodGeneratorConfig::default());
+        assert_ne!(hash(changed.values().next().unwrap()), hash(&content));
+        
+        // Files come in path order, each with a header
+        let files = generate(proto_content, ZodGeneratorConfig { single_file: false, ..ZodGeneratorConfig::default() });
+        let paths: Vec<&str> = files.keys().map(String::as_str).collect();
+        assert_eq!(paths, ["shop/Item.ts", "shop/Order.ts", "shop/index.ts"]);
+        assert!(files.values().all(|content| content.starts_with("// Generated by protobuf_to_zod v")));
     }
-}
\ No newline at end of file
+}
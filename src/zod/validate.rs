@@ -0,0 +1,151 @@
+//! Zod metadata from protovalidate and protoc-gen-validate rules
+//!
+//! Fields that already carry validation rules for
+//! [protovalidate](https://github.com/bufbuild/protovalidate) or the older
+//! [protoc-gen-validate](https://github.com/bufbuild/protoc-gen-validate) are
+//! translated into the equivalent `ZodMetadata` constraints:
+//!
+//! ```protobuf
+//! string name = 1 [(buf.validate.field).string.min_len = 1];
+//! repeated string tags = 2 [(validate.rules).repeated = { max_items: 10 }];
+//! ```
+//!
+//! Rules without a Zod equivalent (CEL expressions, byte lengths, `in` lists)
+//! are skipped.
+
+use crate::parser::ast::ProtoOption;
+use crate::zod::metadata::ZodMetadata;
+use crate::zod::options::rules_from_options;
+use log::debug;
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+
+/// Option name of protovalidate field rules
+pub const PROTOVALIDATE_FIELD_OPTION: &str = "(buf.validate.field)";
+
+/// Option name of protoc-gen-validate field rules
+pub const PGV_FIELD_OPTION: &str = "(validate.rules)";
+
+/// Numeric rule types whose bounds are integers
+const INTEGER_RULES: &[&str] = &[
+    "int32", "int64", "uint32", "uint64", "sint32", "sint64",
+    "fixed32", "fixed64", "sfixed32", "sfixed64",
+];
+
+/// Translate the protovalidate and protoc-gen-validate rules of a field into Zod metadata
+///
+/// Returns `None` if the field carries neither kind of rule. When both are
+/// present, protovalidate rules are applied last.
+pub fn metadata_from_validation_rules(options: &[ProtoOption]) -> Option<ZodMetadata> {
+    let mut metadata = None;
+
+    for extension in [PGV_FIELD_OPTION, PROTOVALIDATE_FIELD_OPTION] {
+        if let Some(rules) = rules_from_options(options, extension) {
+            apply_field_rules(metadata.get_or_insert_with(ZodMetadata::new), &rules);
+        }
+    }
+
+    metadata
+}
+
+/// Apply a `FieldRules` tree to the metadata
+fn apply_field_rules(metadata: &mut ZodMetadata, rules: &Map<String, Value>) {
+    for (kind, value) in rules {
+        match (kind.as_str(), value) {
+            ("required", Value::Bool(true)) => metadata.optional = Some(false),
+            ("string", Value::Object(rules)) => apply_string_rules(metadata, rules),
+            ("float" | "double", Value::Object(rules)) => apply_number_rules(metadata, rules, false),
+            (kind, Value::Object(rules)) if INTEGER_RULES.contains(&kind) => {
+                apply_number_rules(metadata, rules, true)
+            }
+            ("repeated", Value::Object(rules)) => apply_repeated_rules(metadata, rules),
+            // protoc-gen-validate marks required message fields with `message.required`
+            ("message", Value::Object(rules)) => {
+                if rules.get("required") == Some(&Value::Bool(true)) {
+                    metadata.optional = Some(false);
+                }
+            }
+            (kind, _) => debug!("Skipping validation rule without a Zod equivalent: {}", kind),
+        }
+    }
+}
+
+/// Apply `StringRules` (`min_len`, `pattern`, `email`, ...)
+fn apply_string_rules(metadata: &mut ZodMetadata, rules: &Map<String, Value>) {
+    for (rule, value) in rules {
+        match (rule.as_str(), value) {
+            ("min_len", value) => metadata.min = value.as_i64().or(metadata.min),
+            ("max_len", value) => metadata.max = value.as_i64().or(metadata.max),
+            ("len", value) => {
+                if let Some(len) = value.as_i64() {
+                    metadata.min = Some(len);
+                    metadata.max = Some(len);
+                }
+            }
+            ("pattern", Value::String(pattern)) => metadata.regex = Some(pattern.clone()),
+            ("email", Value::Bool(true)) => metadata.email = Some(true),
+            ("uri", Value::Bool(true)) => metadata.url = Some(true),
+            ("uuid", Value::Bool(true)) => metadata.uuid = Some(true),
+            (rule, _) => debug!("Skipping string rule without a Zod equivalent: {}", rule),
+        }
+    }
+}
+
+/// Apply numeric rules (`gt`, `gte`, `lt`, `lte`, `const`)
+///
+/// Bounds are stored as inclusive integers, so exclusive bounds on integer
+/// types are shifted by one. Fractional bounds on floating point types cannot
+/// be represented and are skipped.
+fn apply_number_rules(metadata: &mut ZodMetadata, rules: &Map<String, Value>, integer: bool) {
+    for (rule, value) in rules {
+        let bound = match integral_value(value) {
+            Some(bound) => bound,
+            None => {
+                debug!("Skipping non-integral numeric bound {}: {}", rule, value);
+                continue;
+            }
+        };
+
+        match rule.as_str() {
+            "gte" => metadata.min = Some(bound),
+            "lte" => metadata.max = Some(bound),
+            "gt" if integer => metadata.min = Some(bound + 1),
+            "lt" if integer => metadata.max = Some(bound - 1),
+            "const" => {
+                metadata.min = Some(bound);
+                metadata.max = Some(bound);
+            }
+            rule => debug!("Skipping numeric rule without a Zod equivalent: {}", rule),
+        }
+    }
+}
+
+/// Apply `RepeatedRules` (`min_items`, `max_items`, `items`)
+///
+/// Rules under `items` apply to every element, which is where the generator
+/// puts field-level constraints of repeated fields.
+fn apply_repeated_rules(metadata: &mut ZodMetadata, rules: &Map<String, Value>) {
+    for (rule, value) in rules {
+        match (rule.as_str(), value) {
+            ("min_items", value) | ("max_items", value) if value.is_u64() => {
+                let key = if rule == "min_items" { "min" } else { "max" };
+                metadata
+                    .array
+                    .get_or_insert_with(HashMap::new)
+                    .insert(key.to_string(), value.clone());
+            }
+            ("items", Value::Object(item_rules)) => apply_field_rules(metadata, item_rules),
+            (rule, _) => debug!("Skipping repeated rule without a Zod equivalent: {}", rule),
+        }
+    }
+}
+
+/// Return the value as an integer if it has no fractional part
+fn integral_value(value: &Value) -> Option<i64> {
+    value.as_i64().or_else(|| {
+        value
+            .as_f64()
+            .filter(|f| f.fract() == 0.0 && f.abs() < i64::MAX as f64)
+            .map(|f| f as i64)
+    })
+}
@@ -0,0 +1,215 @@
+//! Zod schemas for the protobuf well-known types
+//!
+//! Types from `google/protobuf/*.proto` are mapped to schemas matching their
+//! [proto3 JSON representation](https://protobuf.dev/programming-guides/json/)
+//! instead of being referenced as (undefined) `google.protobuf.*` identifiers.
+
+use crate::parser::ast::{FieldType, Message};
+use crate::zod::domain::Direction;
+use crate::zod::config::{
+    StructMapping, TimestampMapping, UnknownKeys, WellKnownTypesConfig, WrapperMapping, ZodVersion,
+};
+
+/// Name of the helper schema describing arbitrary JSON values
+pub const JSON_VALUE_SCHEMA: &str = "ProtoJsonValue";
+
+/// The Zod representation of a well-known type
+#[derive(Debug, Clone, PartialEq)]
+pub enum WellKnownSchema {
+    /// A wrapper type: the wrapped scalar, generated like a scalar field and made nullable
+    Wrapper(FieldType),
+
+    /// A complete Zod expression
+    Schema(String),
+}
+
+/// Strip the `google.protobuf.` package from a type reference
+fn well_known_name(type_name: &str) -> Option<&str> {
+    type_name
+        .strip_prefix('.')
+        .unwrap_or(type_name)
+        .strip_prefix("google.protobuf.")
+}
+
+/// Whether a type reference is `google.protobuf.Timestamp`
+pub fn is_timestamp(type_name: &str) -> bool {
+    well_known_name(type_name) == Some("Timestamp")
+}
+
+impl WellKnownTypesConfig {
+    /// Zod representation of a well-known type in the syntax of `version`, or `None` for other types
+    ///
+    /// Domain schemas represent timestamps as `Date`, whatever the timestamp mapping.
+    pub fn schema(&self, type_name: &str, version: ZodVersion, direction: Direction) -> Option<WellKnownSchema> {
+        let name = well_known_name(type_name)?;
+
+        if let Some(schema) = self.overrides.get(&format!("google.protobuf.{}", name)) {
+            return Some(WellKnownSchema::Schema(schema.clone()));
+        }
+
+        let schema = match name {
+            "Timestamp" => match (direction, self.timestamp) {
+                (Direction::Encode, _) => version.transform("z.date()", "(value) => value.toISOString()"),
+                (Direction::Wire, TimestampMapping::String) => version.datetime().to_string(),
+                (_, TimestampMapping::Date) | (Direction::Decode, _) => {
+                    version.pipe(version.datetime(), "z.coerce.date()")
+                }
+            },
+            // Seconds with up to nine fractional digits, e.g. "1.5s"
+            "Duration" => version.regex("z.string()", r"/^-?\d+(\.\d{1,9})?s$/"),
+            "Any" => version.object(r#"{ "@type": z.string() }"#, &UnknownKeys::Passthrough),
+            "Empty" => "z.object({})".to_string(),
+            "FieldMask" => "z.string()".to_string(),
+            "Struct" => match self.r#struct {
+                StructMapping::Json => format!("z.record(z.string(), {})", JSON_VALUE_SCHEMA),
+                StructMapping::Unknown => "z.record(z.string(), z.unknown())".to_string(),
+            },
+            "Value" => match self.r#struct {
+                StructMapping::Json => JSON_VALUE_SCHEMA.to_string(),
+                StructMapping::Unknown => "z.unknown()".to_string(),
+            },
+            "ListValue" => match self.r#struct {
+                StructMapping::Json => format!("z.array({})", JSON_VALUE_SCHEMA),
+                StructMapping::Unknown => "z.array(z.unknown())".to_string(),
+            },
+            "DoubleValue" => return Some(WellKnownSchema::Wrapper(FieldType::Double)),
+            "FloatValue" => return Some(WellKnownSchema::Wrapper(FieldType::Float)),
+            "Int64Value" => return Some(WellKnownSchema::Wrapper(FieldType::Int64)),
+            "UInt64Value" => return Some(WellKnownSchema::Wrapper(FieldType::UInt64)),
+            "Int32Value" => return Some(WellKnownSchema::Wrapper(FieldType::Int32)),
+            "UInt32Value" => return Some(WellKnownSchema::Wrapper(FieldType::UInt32)),
+            "BoolValue" => return Some(WellKnownSchema::Wrapper(FieldType::Bool)),
+            "StringValue" => return Some(WellKnownSchema::Wrapper(FieldType::String)),
+            "BytesValue" => return Some(WellKnownSchema::Wrapper(FieldType::Bytes)),
+            _ => return None,
+        };
+
+        Some(WellKnownSchema::Schema(schema))
+    }
+
+    /// Modifier making a wrapper type's scalar accept `null`
+    pub fn wrapper_modifier(&self) -> &'static str {
+        match self.wrappers {
+            WrapperMapping::Nullable => "nullable",
+            WrapperMapping::Nullish => "nullish",
+        }
+    }
+
+    /// TypeScript type of a well-known type (other than the wrappers), or `None` for other types
+    ///
+    /// Overridden mappings are typed as `unknown`. `domain` selects the type of domain values.
+    pub fn ts_type(&self, type_name: &str, domain: bool) -> Option<String> {
+        let name = well_known_name(type_name)?;
+
+        if self.overrides.contains_key(&format!("google.protobuf.{}", name)) {
+            return Some("unknown".to_string());
+        }
+
+        let json = self.r#struct == StructMapping::Json;
+        let ts_type = match name {
+            "Timestamp" if domain || self.timestamp == TimestampMapping::Date => "Date".to_string(),
+            "Timestamp" | "Duration" | "FieldMask" => "string".to_string(),
+            "Any" => r#"{ "@type": string; [key: string]: unknown }"#.to_string(),
+            "Empty" => "Record<string, never>".to_string(),
+            "Struct" if json => format!("Record<string, {}>", JSON_VALUE_SCHEMA),
+            "Value" if json => JSON_VALUE_SCHEMA.to_string(),
+            "ListValue" if json => format!("{}[]", JSON_VALUE_SCHEMA),
+            "Struct" => "Record<string, unknown>".to_string(),
+            "Value" => "unknown".to_string(),
+            "ListValue" => "unknown[]".to_string(),
+            _ => return None,
+        };
+
+        Some(ts_type)
+    }
+
+    /// Whether any field of the messages needs the JSON value helper schema
+    pub fn uses_json_value(&self, messages: &[Message]) -> bool {
+        messages.iter().any(|message| {
+            let oneof_fields = message.oneofs.iter().flat_map(|oneof| &oneof.fields);
+            let uses_json = message.fields.iter().chain(oneof_fields).any(|field| {
+                let typ = match field.typ {
+                    FieldType::Map(_, ref value_type) => value_type.as_ref(),
+                    ref typ => typ,
+                };
+                matches!(
+                    typ,
+                    FieldType::MessageOrEnum(type_name)
+                        if self.schema(type_name, ZodVersion::default(), Direction::Wire).is_some_and(|schema| matches!(
+                            schema,
+                            WellKnownSchema::Schema(ref schema) if schema.contains(JSON_VALUE_SCHEMA)
+                        ))
+                )
+            });
+            uses_json || self.uses_json_value(&message.nested_messages)
+        })
+    }
+}
+
+/// Helper schema for arbitrary JSON values, used by `Struct`, `Value` and `ListValue`
+pub fn json_value_schema(version: ZodVersion) -> String {
+    format!(
+        concat!(
+            "type {0} =\n",
+            "  | string\n",
+            "  | number\n",
+            "  | boolean\n",
+            "  | null\n",
+            "  | {0}[]\n",
+            "  | {{ [key: string]: {0} }};\n",
+            "const {0}: {1} = z.lazy(() =>\n",
+            "  z.union([\n",
+            "    z.string(),\n",
+            "    z.number(),\n",
+            "    z.boolean(),\n",
+            "    z.null(),\n",
+            "    z.array({0}),\n",
+            "    z.record(z.string(), {0}),\n",
+            "  ]),\n",
+            ");",
+        ),
+        JSON_VALUE_SCHEMA,
+        version.type_annotation(JSON_VALUE_SCHEMA, None)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_well_known_schemas() {
+        let mut config = WellKnownTypesConfig::default();
+
+        assert_eq!(
+            config.schema("google.protobuf.Timestamp", ZodVersion::V3, Direction::Wire),
+            Some(WellKnownSchema::Schema("z.string().datetime({ offset: true })".to_string()))
+        );
+        assert_eq!(
+            config.schema(".google.protobuf.BoolValue", ZodVersion::V3, Direction::Wire),
+            Some(WellKnownSchema::Wrapper(FieldType::Bool))
+        );
+        assert_eq!(config.schema("Timestamp", ZodVersion::V3, Direction::Wire), None);
+        assert_eq!(config.schema("google.protobuf.FileDescriptorProto", ZodVersion::V3, Direction::Wire), None);
+
+        config.timestamp = TimestampMapping::Date;
+        config.overrides.insert("google.protobuf.Duration".to_string(), "z.string()".to_string());
+        assert_eq!(
+            config.schema("google.protobuf.Timestamp", ZodVersion::V3, Direction::Wire),
+            Some(WellKnownSchema::Schema(
+                "z.string().datetime({ offset: true }).pipe(z.coerce.date())".to_string()
+            ))
+        );
+        assert_eq!(
+            config.schema("google.protobuf.Duration", ZodVersion::V3, Direction::Wire),
+            Some(WellKnownSchema::Schema("z.string()".to_string()))
+        );
+        assert_eq!(config.ts_type("google.protobuf.Timestamp", false), Some("Date".to_string()));
+        assert_eq!(
+            config.schema("google.protobuf.Timestamp", ZodVersion::Mini, Direction::Wire),
+            Some(WellKnownSchema::Schema(
+                "z.pipe(z.iso.datetime({ offset: true }), z.coerce.date())".to_string()
+            ))
+        );
+    }
+}